@@ -0,0 +1,111 @@
+//! Sustained throughput of small `GetObject` requests through cloned operators.
+//!
+//! The requests are served by a local keep-alive HTTP server, so the result measures the overhead of
+//! the SDK and the connection reuse rather than the network.
+//! The number of opened connections is checked to catch a regression where the clones stop
+//! sharing the connection pool.
+//!
+//! ```sh
+//! cargo bench --bench get_throughput
+//! ```
+use cf_r2_sdk::builder::Builder;
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Number of concurrent tasks, each with its own clone of the operator.
+const TASKS: usize = 16;
+/// Size of the served body.
+const BODY_SIZE: usize = 1024;
+
+/// Serve a [BODY_SIZE] body to every request on keep-alive connections, and count the connections.
+async fn serve() -> (String, Arc<AtomicUsize>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+    let accepted = connections.clone();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{}",
+        BODY_SIZE,
+        "x".repeat(BODY_SIZE)
+    );
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            accepted.fetch_add(1, Ordering::Relaxed);
+            let response = response.clone();
+            tokio::spawn(async move {
+                let mut request = Vec::new();
+                let mut buffer = [0; 4096];
+                loop {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buffer[..n]),
+                    }
+                    if request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        request.clear();
+                        if stream.write_all(response.as_bytes()).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+    });
+    (endpoint, connections)
+}
+
+/// Send `requests` GET requests spread over [TASKS] clones, and return the elapsed time.
+async fn run(object: &cf_r2_sdk::operator::Operator, requests: u64) -> Duration {
+    let start = Instant::now();
+    let tasks = (0..TASKS as u64).map(|task| {
+        let object = object.clone();
+        // the first tasks take the remainder
+        let requests = requests / TASKS as u64 + u64::from(task < requests % TASKS as u64);
+        tokio::spawn(async move {
+            for _ in 0..requests {
+                object.download("bench.txt").await.unwrap();
+            }
+        })
+    });
+    for task in futures::future::join_all(tasks).await {
+        task.unwrap();
+    }
+    start.elapsed()
+}
+
+fn get_throughput(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (endpoint, connections) = runtime.block_on(serve());
+    let object = Builder::new()
+        .set_bucket_name("bucket".to_string())
+        .set_access_key_id("access_key_id".to_string())
+        .set_secret_access_key("secret_access_key".to_string())
+        .set_endpoint(endpoint)
+        .set_force_path_style(true)
+        .create_client_result()
+        .unwrap();
+
+    let mut group = c.benchmark_group("get_throughput");
+    group.throughput(Throughput::Bytes(BODY_SIZE as u64));
+    group.bench_function("download", |b| {
+        b.to_async(&runtime)
+            .iter_custom(|requests| run(&object, requests));
+    });
+    group.finish();
+
+    assert!(
+        connections.load(Ordering::Relaxed) <= TASKS,
+        "the clones of the operator do not share the connection pool ({} connections for {} tasks)",
+        connections.load(Ordering::Relaxed),
+        TASKS
+    );
+}
+
+criterion_group!(benches, get_throughput);
+criterion_main!(benches);
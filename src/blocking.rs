@@ -0,0 +1,122 @@
+//! Blocking wrapper of [Operator](crate::operator::Operator) for programs without an async runtime,
+//! e.g. a CLI.
+//!
+//! The operations run on a single multi-thread `tokio` runtime created on the first call and
+//! reused afterwards, or on the runtime set by
+//! [Builder::set_runtime_handle](crate::builder::Builder::set_runtime_handle).
+//! The methods must not be called from an async context, since blocking inside a runtime panics.
+//!
+//! Requires the `blocking` feature.
+//!
+//! ```no_run
+//! use cf_r2_sdk::builder::Builder;
+//! use cf_r2_sdk::error::Error;
+//!
+//! fn main() -> Result<(), Error> {
+//!     let object = Builder::new()
+//!         .set_bucket_name("bucket_name".to_string())
+//!         .set_access_key_id("access_key_id".to_string())
+//!         .set_secret_access_key("secret_access_key".to_string())
+//!         .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+//!         .create_blocking_client_result()?;
+//!
+//!     object.upload_binary("hello.txt", "text/plain", b"Hello, World!", None)?;
+//!     let bytes = object.download("hello.txt")?;
+//!     assert_eq!(bytes, b"Hello, World!");
+//!     object.delete("hello.txt")?;
+//!     Ok(())
+//! }
+//! ```
+use crate::{error::OperationError, object::ObjectMetadata, operator::check_content_type};
+use std::{future::Future, path::Path, sync::OnceLock};
+use tokio::runtime::{Handle, Runtime};
+
+/// Runtime shared by the blocking operators without a runtime handle.
+static SHARED_RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// Blocking version of [Operator](crate::operator::Operator).
+#[derive(Debug, Clone)]
+pub struct Operator {
+    operator: crate::operator::Operator,
+    handle: Option<Handle>,
+}
+
+impl Operator {
+    pub fn new(operator: crate::operator::Operator, handle: Option<Handle>) -> Self {
+        //! Create a new blocking [Operator] running `operator` on `handle`,
+        //! or on the shared runtime if `handle` is `None`.
+        Self { operator, handle }
+    }
+
+    pub fn as_async(&self) -> &crate::operator::Operator {
+        //! Get the async [Operator](crate::operator::Operator) for the operations without a blocking version.
+        &self.operator
+    }
+
+    /// Run `future` to completion on the runtime of this operator.
+    pub(crate) fn block_on<F: Future>(&self, future: F) -> F::Output {
+        match &self.handle {
+            Some(handle) => handle.block_on(future),
+            None => SHARED_RUNTIME
+                .get_or_init(|| {
+                    tokio::runtime::Builder::new_multi_thread()
+                        .enable_all()
+                        .build()
+                        .expect("failed to create the tokio runtime of the blocking operator")
+                })
+                .block_on(future),
+        }
+    }
+
+    pub fn upload_file(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        file_path: impl AsRef<Path>,
+        cache_control: Option<&str>,
+    ) -> Result<(), OperationError> {
+        //! Upload a file to the R2 bucket. See [Operator::upload_file](crate::operator::Operator::upload_file).
+        //!
+        //! The file is read with `std::fs` on the calling thread, so only the request runs on the runtime.
+        check_content_type(mime_type)?;
+        let binary = std::fs::read(file_path)?;
+        self.block_on(
+            self.operator
+                .upload_binary(file_name, mime_type, &binary, cache_control),
+        )
+    }
+
+    pub fn upload_binary(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        binary: &[u8],
+        cache_control: Option<&str>,
+    ) -> Result<(), OperationError> {
+        //! Upload binary data to the R2 bucket. See [Operator::upload_binary](crate::operator::Operator::upload_binary).
+        self.block_on(
+            self.operator
+                .upload_binary(file_name, mime_type, binary, cache_control),
+        )
+    }
+
+    pub fn download(&self, file_name: &str) -> Result<Vec<u8>, OperationError> {
+        //! Download a file as binary data from the R2 bucket. See [Operator::download](crate::operator::Operator::download).
+        self.block_on(self.operator.download(file_name))
+    }
+
+    pub fn head_object(&self, file_name: &str) -> Result<ObjectMetadata, OperationError> {
+        //! Get the metadata of a file in the R2 bucket. See [Operator::head_object](crate::operator::Operator::head_object).
+        self.block_on(self.operator.head_object(file_name))
+    }
+
+    pub fn delete(&self, file_name: &str) -> Result<(), OperationError> {
+        //! Delete a file from the R2 bucket. See [Operator::delete](crate::operator::Operator::delete).
+        self.block_on(self.operator.delete(file_name))
+    }
+
+    pub fn list_objects(&self) -> Result<Vec<String>, OperationError> {
+        //! List the file names in the R2 bucket. See [Operator::list_objects](crate::operator::Operator::list_objects).
+        self.block_on(self.operator.list_objects())
+    }
+}
@@ -0,0 +1,449 @@
+use crate::{
+    error::{OperationError, R2Error},
+    object::{CorsRule, LifecycleRule},
+    operator::{attach_content_md5, Operator},
+};
+use aws_sdk_s3::{
+    error::ProvideErrorMetadata,
+    types::{
+        AbortIncompleteMultipartUpload, BucketLifecycleConfiguration, CorsConfiguration,
+        CorsRule as S3CorsRule, ExpirationStatus, LifecycleExpiration,
+        LifecycleRule as S3LifecycleRule, LifecycleRuleFilter,
+    },
+};
+
+impl Operator {
+    pub async fn get_bucket_cors(&self) -> Result<Vec<CorsRule>, OperationError> {
+        //! Get the CORS rules of the R2 bucket.
+        //!
+        //! A bucket without a CORS configuration returns an empty vector.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    let rules = object.get_bucket_cors().await?;
+        //!
+        //!    for rule in rules {
+        //!        println!("{:?} {:?}", rule.allowed_origins, rule.allowed_methods);
+        //!    }
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("get_bucket_cors", async {
+            match self
+                .client
+                .get_bucket_cors()
+                .bucket(&self.bucket_name)
+                .send()
+                .await
+            {
+                Ok(output) => Ok(output
+                    .cors_rules()
+                    .iter()
+                    .map(|rule| CorsRule {
+                        allowed_origins: rule.allowed_origins().to_vec(),
+                        allowed_methods: rule.allowed_methods().to_vec(),
+                        allowed_headers: rule.allowed_headers().to_vec(),
+                        expose_headers: rule.expose_headers().to_vec(),
+                        max_age_seconds: rule.max_age_seconds(),
+                    })
+                    .collect()),
+                Err(err) if err.code() == Some("NoSuchCORSConfiguration") => Ok(Vec::new()),
+                Err(err) => Err(OperationError::AWSSdkS3GetBucketCorsError(err.into())),
+            }
+        })
+        .await
+    }
+
+    pub async fn put_bucket_cors(&self, rules: &[CorsRule]) -> Result<(), OperationError> {
+        //! Replace the CORS rules of the R2 bucket with `rules`.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::object::CorsRule;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // allow a static site to fetch files
+        //!    object
+        //!        .put_bucket_cors(&[CorsRule {
+        //!            allowed_origins: vec!["https://example.com".to_string()],
+        //!            allowed_methods: vec!["GET".to_string(), "HEAD".to_string()],
+        //!            allowed_headers: vec!["*".to_string()],
+        //!            max_age_seconds: Some(3600),
+        //!            ..Default::default()
+        //!        }])
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete_bucket_cors().await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("put_bucket_cors", async {
+            let mut cors_rules = Vec::with_capacity(rules.len());
+            for rule in rules {
+                let cors_rule = S3CorsRule::builder()
+                    .set_allowed_origins(Some(rule.allowed_origins.clone()))
+                    .set_allowed_methods(Some(rule.allowed_methods.clone()))
+                    .set_allowed_headers(Some(rule.allowed_headers.clone()))
+                    .set_expose_headers(Some(rule.expose_headers.clone()))
+                    .set_max_age_seconds(rule.max_age_seconds)
+                    .build();
+                match cors_rule {
+                    Ok(cors_rule) => cors_rules.push(cors_rule),
+                    Err(err) => {
+                        return Err(OperationError::AWSSdkS3PutBucketCorsError(R2Error::new(
+                            err.to_string(),
+                        )))
+                    }
+                }
+            }
+            let configuration = match CorsConfiguration::builder()
+                .set_cors_rules(Some(cors_rules))
+                .build()
+            {
+                Ok(configuration) => configuration,
+                Err(err) => {
+                    return Err(OperationError::AWSSdkS3PutBucketCorsError(R2Error::new(
+                        err.to_string(),
+                    )))
+                }
+            };
+            match self
+                .client
+                .put_bucket_cors()
+                .bucket(&self.bucket_name)
+                .cors_configuration(configuration)
+                .customize()
+                .mutate_request(attach_content_md5)
+                .send()
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(err) => Err(OperationError::AWSSdkS3PutBucketCorsError(err.into())),
+            }
+        })
+        .await
+    }
+
+    pub async fn delete_bucket_cors(&self) -> Result<(), OperationError> {
+        //! Delete all CORS rules of the R2 bucket.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! #
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .set_region("auto".to_string())
+        //! #     .create_client_result()?;
+        //! object.delete_bucket_cors().await?;
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("delete_bucket_cors", async {
+            match self
+                .client
+                .delete_bucket_cors()
+                .bucket(&self.bucket_name)
+                .send()
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(err) => Err(OperationError::AWSSdkS3DeleteBucketCorsError(err.into())),
+            }
+        })
+        .await
+    }
+
+    pub async fn get_bucket_lifecycle(&self) -> Result<Vec<LifecycleRule>, OperationError> {
+        //! Get the lifecycle rules of the R2 bucket.
+        //!
+        //! A bucket without a lifecycle configuration returns an empty vector.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    let rules = object.get_bucket_lifecycle().await?;
+        //!
+        //!    for rule in rules {
+        //!        println!("{} {:?}", rule.id, rule.expiration_days);
+        //!    }
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("get_bucket_lifecycle", async {
+            match self
+                .client
+                .get_bucket_lifecycle_configuration()
+                .bucket(&self.bucket_name)
+                .send()
+                .await
+            {
+                Ok(output) => Ok(output
+                    .rules()
+                    .iter()
+                    .map(|rule| LifecycleRule {
+                        id: rule.id().unwrap_or_default().to_owned(),
+                        prefix: rule
+                            .filter()
+                            .and_then(|filter| filter.prefix())
+                            .unwrap_or_default()
+                            .to_owned(),
+                        enabled: *rule.status() == ExpirationStatus::Enabled,
+                        expiration_days: rule.expiration().and_then(|expiration| expiration.days()),
+                        abort_incomplete_multipart_upload_days: rule
+                            .abort_incomplete_multipart_upload()
+                            .and_then(|abort| abort.days_after_initiation()),
+                    })
+                    .collect()),
+                Err(err) if err.code() == Some("NoSuchLifecycleConfiguration") => Ok(Vec::new()),
+                Err(err) => {
+                    Err(OperationError::AWSSdkS3GetBucketLifecycleConfigurationError(err.into()))
+                }
+            }
+        })
+        .await
+    }
+
+    pub async fn put_bucket_lifecycle(
+        &self,
+        rules: &[LifecycleRule],
+    ) -> Result<(), OperationError> {
+        //! Replace the lifecycle rules of the R2 bucket with `rules`.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::object::LifecycleRule;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // delete `tmp/` files after 7 days
+        //!    object
+        //!        .put_bucket_lifecycle(&[LifecycleRule::new("doctest-expire-tmp")
+        //!            .set_prefix("tmp/")
+        //!            .set_expiration_days(7)])
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete_bucket_lifecycle().await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("put_bucket_lifecycle", async {
+            let mut lifecycle_rules = Vec::with_capacity(rules.len());
+            for rule in rules {
+                let lifecycle_rule = S3LifecycleRule::builder()
+                    .id(&rule.id)
+                    .filter(LifecycleRuleFilter::builder().prefix(&rule.prefix).build())
+                    .status(if rule.enabled {
+                        ExpirationStatus::Enabled
+                    } else {
+                        ExpirationStatus::Disabled
+                    })
+                    .set_expiration(
+                        rule.expiration_days
+                            .map(|days| LifecycleExpiration::builder().days(days).build()),
+                    )
+                    .set_abort_incomplete_multipart_upload(
+                        rule.abort_incomplete_multipart_upload_days.map(|days| {
+                            AbortIncompleteMultipartUpload::builder()
+                                .days_after_initiation(days)
+                                .build()
+                        }),
+                    )
+                    .build();
+                match lifecycle_rule {
+                    Ok(lifecycle_rule) => lifecycle_rules.push(lifecycle_rule),
+                    Err(err) => {
+                        return Err(
+                            OperationError::AWSSdkS3PutBucketLifecycleConfigurationError(
+                                R2Error::new(err.to_string()),
+                            ),
+                        )
+                    }
+                }
+            }
+            let configuration = match BucketLifecycleConfiguration::builder()
+                .set_rules(Some(lifecycle_rules))
+                .build()
+            {
+                Ok(configuration) => configuration,
+                Err(err) => {
+                    return Err(
+                        OperationError::AWSSdkS3PutBucketLifecycleConfigurationError(R2Error::new(
+                            err.to_string(),
+                        )),
+                    )
+                }
+            };
+            match self
+                .client
+                .put_bucket_lifecycle_configuration()
+                .bucket(&self.bucket_name)
+                .lifecycle_configuration(configuration)
+                .customize()
+                .mutate_request(attach_content_md5)
+                .send()
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(err) => {
+                    Err(OperationError::AWSSdkS3PutBucketLifecycleConfigurationError(err.into()))
+                }
+            }
+        })
+        .await
+    }
+
+    pub async fn delete_bucket_lifecycle(&self) -> Result<(), OperationError> {
+        //! Delete all lifecycle rules of the R2 bucket.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! #
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .set_region("auto".to_string())
+        //! #     .create_client_result()?;
+        //! object.delete_bucket_lifecycle().await?;
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("delete_bucket_lifecycle", async {
+            match self
+                .client
+                .delete_bucket_lifecycle()
+                .bucket(&self.bucket_name)
+                .send()
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(err) => Err(OperationError::AWSSdkS3DeleteBucketLifecycleError(
+                    err.into(),
+                )),
+            }
+        })
+        .await
+    }
+}
@@ -1,7 +1,20 @@
-use crate::{error::BuilderError, operator::Operator};
+use crate::{
+    error::{BuilderError, R2Error, S3ErrorCode},
+    operator::{Operator, DEFAULT_MAX_ATTEMPTS},
+};
 use aws_sdk_s3::config::{
-    Credentials, Region, RequestChecksumCalculation, ResponseChecksumValidation,
+    http::HttpRequest,
+    interceptors::{BeforeTransmitInterceptorContextMut, InterceptorContext},
+    retry::{ClassifyRetry, RetryAction, RetryConfig},
+    timeout::TimeoutConfig,
+    ConfigBag, Credentials, Intercept, ProvideCredentials, Region, RequestChecksumCalculation,
+    ResponseChecksumValidation, RuntimeComponents, SharedCredentialsProvider,
+};
+use aws_smithy_types::{
+    endpoint::{Endpoint, EndpointAuthScheme},
+    Document,
 };
+use std::{sync::Arc, time::Duration};
 
 /// Builder for creating a new [Operator] instance.
 ///
@@ -43,7 +56,25 @@ pub struct Builder {
     access_key_id: Option<String>,
     secret_access_key: Option<String>,
     endpoint: Option<String>,
+    public_base_url: Option<String>,
     region: String,
+    signing_region: Option<String>,
+    credentials_provider: Option<SharedCredentialsProvider>,
+    max_attempts: u32,
+    attempt_timeout: Option<Duration>,
+    operation_timeout: Option<Duration>,
+    rate_limit: Option<u64>,
+    #[cfg(feature = "head-cache")]
+    head_cache: Option<(usize, Duration)>,
+    verify_integrity: bool,
+    request_payer: bool,
+    allow_insecure: bool,
+    force_path_style: bool,
+    retry_classifier: Option<RetryClassifier>,
+    retry_signature_errors: bool,
+    headers: Vec<(String, HeaderValue)>,
+    #[cfg(feature = "blocking")]
+    runtime_handle: Option<tokio::runtime::Handle>,
 }
 
 impl Default for Builder {
@@ -53,7 +84,25 @@ impl Default for Builder {
             access_key_id: None,
             secret_access_key: None,
             endpoint: None,
+            public_base_url: None,
             region: "auto".to_string(),
+            signing_region: None,
+            credentials_provider: None,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            attempt_timeout: None,
+            operation_timeout: None,
+            rate_limit: None,
+            #[cfg(feature = "head-cache")]
+            head_cache: None,
+            verify_integrity: false,
+            request_payer: false,
+            allow_insecure: false,
+            force_path_style: false,
+            retry_classifier: None,
+            retry_signature_errors: false,
+            headers: Vec::new(),
+            #[cfg(feature = "blocking")]
+            runtime_handle: None,
         }
     }
 }
@@ -66,6 +115,79 @@ impl Builder {
         Self::default()
     }
 
+    #[cfg(feature = "profile")]
+    pub fn from_profile(profile_name: &str) -> Self {
+        //! Create a new [Builder] instance that loads the credentials from a named profile.
+        //!
+        //! The credentials are read from the shared AWS config and credentials files
+        //! (e.g. `~/.aws/credentials`), so the access key id and the secret access key do not need to be set.
+        //! The bucket name and the endpoint are still required.
+        //!
+        //! Requires the `profile` feature.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //!
+        //! fn main() -> Result<(), Error> {
+        //!     let object: cf_r2_sdk::operator::Operator = Builder::from_profile("r2")
+        //!         .set_bucket_name("bucket_name".to_string())
+        //!         .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //!         .create_client_result()?;
+        //!     Ok(())
+        //! }
+        //! ```
+        let credentials_provider = aws_config::profile::ProfileFileCredentialsProvider::builder()
+            .profile_name(profile_name)
+            .build();
+        Self {
+            credentials_provider: Some(SharedCredentialsProvider::new(credentials_provider)),
+            ..Self::default()
+        }
+    }
+
+    pub fn r2(
+        account_id: &str,
+        access_key_id: &str,
+        secret_access_key: &str,
+        bucket_name: &str,
+    ) -> Result<Operator, BuilderError> {
+        //! Create a new [Operator] instance for a Cloudflare R2 bucket with the standard configuration.
+        //!
+        //! The endpoint is `https://<account_id>.r2.cloudflarestorage.com` and the region is `auto`.
+        //! Use the setters of [Builder] for other options (e.g. the EU jurisdiction endpoint or retries).
+        //!
+        //! Return [BuilderError::InvalidAccountIdError] if `account_id` is not alphanumeric.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //!
+        //! fn main() -> Result<(), Error> {
+        //!     let object: cf_r2_sdk::operator::Operator = Builder::r2(
+        //!         "0123456789abcdef0123456789abcdef",
+        //!         "access_key_id",
+        //!         "secret_access_key",
+        //!         "bucket_name",
+        //!     )?;
+        //!     Ok(())
+        //! }
+        //! ```
+        if account_id.is_empty() || !account_id.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(BuilderError::InvalidAccountIdError(account_id.to_string()));
+        }
+        Self::new()
+            .set_bucket_name(bucket_name.to_string())
+            .set_access_key_id(access_key_id.to_string())
+            .set_secret_access_key(secret_access_key.to_string())
+            .set_endpoint(format!("https://{}.r2.cloudflarestorage.com", account_id))
+            .create_client_result()
+    }
+
     pub fn set_bucket_name(mut self, bucket_name: String) -> Self {
         //! Set the bucket name.
         self.bucket_name = Some(bucket_name);
@@ -84,18 +206,379 @@ impl Builder {
         self
     }
 
+    pub fn set_credentials_provider(
+        mut self,
+        credentials_provider: impl ProvideCredentials + 'static,
+    ) -> Self {
+        //! Set a credentials provider used instead of the access key id and the secret access key.
+        //!
+        //! The provider is asked for credentials by the SDK (which caches them until they expire),
+        //! so rotated credentials are picked up without creating a new [Operator].
+        //! When it is set, the access key id and the secret access key do not need to be set.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! use aws_sdk_s3::config::Credentials;
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //!
+        //! fn main() -> Result<(), Error> {
+        //!     // any implementation of `ProvideCredentials` can be used, e.g. one reading a secret store
+        //!     let credentials = Credentials::new("access_key_id", "secret_access_key", None, None, "static");
+        //!     let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!         .set_bucket_name("bucket_name".to_string())
+        //!         .set_credentials_provider(credentials)
+        //!         .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //!         .create_client_result()?;
+        //!     Ok(())
+        //! }
+        //! ```
+        self.credentials_provider = Some(SharedCredentialsProvider::new(credentials_provider));
+        self
+    }
+
     pub fn set_endpoint(mut self, endpoint: String) -> Self {
         //! Set the endpoint.
         self.endpoint = Some(endpoint);
         self
     }
 
+    pub fn set_public_base_url(mut self, public_base_url: String) -> Self {
+        //! Set the public base URL of the bucket (e.g. a custom domain or `https://pub-<hash>.r2.dev`).
+        //!
+        //! It is used by [Operator::public_url] to build the URL of a file.
+        self.public_base_url = Some(public_base_url);
+        self
+    }
+
     pub fn set_region(mut self, region: String) -> Self {
         //! Set the region.
         self.region = region;
         self
     }
 
+    pub fn set_signing_region(mut self, signing_region: String) -> Self {
+        //! Set the SigV4 signing region independently of the region.
+        //!
+        //! Only the credential scope of the signature (`Authorization: ... Credential=<key>/<date>/<signing region>/s3/aws4_request`)
+        //! uses the signing region; the region is still used for everything else, e.g. resolving the endpoint.
+        //! When it is not set, the region is used for signing.
+        self.signing_region = Some(signing_region);
+        self
+    }
+
+    pub fn set_max_attempts(mut self, max_attempts: u32) -> Self {
+        //! Set the max attempts of each request, including the first attempt.
+        //!
+        //! This is used by the retry of aws-sdk-s3 and by the retry of the body read in [Operator::download].
+        //! default value of max attempts is 3.
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn set_retry_classifier<F>(mut self, retry_classifier: F) -> Self
+    where
+        F: Fn(&R2Error) -> bool + Send + Sync + 'static,
+    {
+        //! Retry the failed requests for which `retry_classifier` returns true,
+        //! in addition to the errors retried by default (e.g. 5xx, `SlowDown` and timeouts).
+        //!
+        //! The closure receives the status code, the error code (`<Code>` of the response body)
+        //! and the message of each failed attempt.
+        //! It applies to every request, including the ones made by the batch helpers
+        //! (e.g. [Operator::delete_objects], [Operator::upload_files]),
+        //! within the max attempts set by [Builder::set_max_attempts].
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::R2Error;
+        //!
+        //! // a proxy in front of R2 returns 499 when it is busy
+        //! let builder = Builder::new().set_retry_classifier(|err: &R2Error| err.status == Some(499));
+        //! ```
+        self.retry_classifier = Some(RetryClassifier(Arc::new(retry_classifier)));
+        self
+    }
+
+    pub fn set_retry_signature_errors(mut self, retry_signature_errors: bool) -> Self {
+        //! Retry the requests rejected with `SignatureDoesNotMatch` or `RequestTimeTooSkewed`.
+        //!
+        //! These errors are usually permanent (e.g. a wrong secret access key), but they are also
+        //! returned when the clock of the machine is skewed.
+        //! Each attempt is signed again, with the time corrected by the `Date` header of the
+        //! rejected response, so the retry succeeds if the skew was the cause.
+        //! The retries are within the max attempts set by [Builder::set_max_attempts];
+        //! set it to 2 to retry only once.
+        //! The error of the last attempt is still returned as [OperationError::AuthError](crate::error::OperationError::AuthError).
+        //!
+        //! default value is false.
+        self.retry_signature_errors = retry_signature_errors;
+        self
+    }
+
+    pub fn set_attempt_timeout(mut self, attempt_timeout: Duration) -> Self {
+        //! Set the timeout of each attempt of a request.
+        //!
+        //! A stalled attempt fails after this timeout and is retried
+        //! within the max attempts set by [Builder::set_max_attempts].
+        //!
+        //! default is the default of aws-sdk-s3 (no timeout).
+        self.attempt_timeout = Some(attempt_timeout);
+        self
+    }
+
+    pub fn set_operation_timeout(mut self, operation_timeout: Duration) -> Self {
+        //! Set the timeout of a whole request, including all of its retries.
+        //!
+        //! default is the default of aws-sdk-s3 (no timeout).
+        self.operation_timeout = Some(operation_timeout);
+        self
+    }
+
+    pub fn set_rate_limit(mut self, bytes_per_sec: u64) -> Self {
+        //! Limit the average bandwidth of uploads and downloads to `bytes_per_sec`.
+        //!
+        //! The limit is shared by the clones of the created [Operator].
+        //! Downloads are throttled chunk by chunk while reading the body,
+        //! and uploads chunk by chunk while sending the body.
+        //! The reader returned by [Operator::get_range_stream] is not throttled.
+        //!
+        //! default is no limit.
+        self.rate_limit = Some(bytes_per_sec);
+        self
+    }
+
+    #[cfg(feature = "head-cache")]
+    pub fn set_head_cache(mut self, capacity: usize, max_age: Duration) -> Self {
+        //! Cache the metadata of up to `capacity` files for [Operator::head_object_cached].
+        //!
+        //! Cached metadata younger than `max_age` is returned without a request,
+        //! and older metadata is revalidated with its ETag.
+        //! The least recently used file is evicted when the cache is full.
+        //! The cache is shared by the clones of the created [Operator].
+        //!
+        //! default is no cache.
+        self.head_cache = Some((capacity, max_age));
+        self
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn set_runtime_handle(mut self, runtime_handle: tokio::runtime::Handle) -> Self {
+        //! Run the operations of the [blocking::Operator](crate::blocking::Operator) on an existing runtime.
+        //!
+        //! default is a multi-thread runtime created on the first blocking call and shared by all the blocking operators.
+        //!
+        //! Requires the `blocking` feature.
+        self.runtime_handle = Some(runtime_handle);
+        self
+    }
+
+    pub fn set_verify_integrity(mut self, verify_integrity: bool) -> Self {
+        //! Verify each upload by comparing the returned ETag with the MD5 computed locally.
+        //!
+        //! A multipart upload is compared with the composite ETag
+        //! (the MD5 of the part digests followed by `-<number of parts>`).
+        //! A mismatch returns [OperationError::IntegrityMismatch](crate::error::OperationError::IntegrityMismatch).
+        //!
+        //! default value of verify integrity is false.
+        self.verify_integrity = verify_integrity;
+        self
+    }
+
+    pub fn set_request_payer(mut self, request_payer: bool) -> Self {
+        //! Send `x-amz-request-payer: requester` with the object requests
+        //! (get, head, put, copy, list, delete and multipart uploads).
+        //!
+        //! Buckets with requester pays enabled deny the requests without this header,
+        //! which surfaces as an `AccessDenied` error.
+        //!
+        //! default value of request payer is false.
+        self.request_payer = request_payer;
+        self
+    }
+
+    pub fn set_header(mut self, name: &str, value: &str) -> Self {
+        //! Send the header `name: value` with every request, e.g. a correlation ID of your tracing system.
+        //!
+        //! An invalid header name or value makes [Builder::create_client_result] return
+        //! [BuilderError::InvalidHeaderError].
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //!
+        //! let builder = Builder::new().set_header("x-correlation-id", "deploy-2024-06-01");
+        //! ```
+        self.headers
+            .push((name.to_string(), HeaderValue::Static(value.to_string())));
+        self
+    }
+
+    pub fn set_header_fn<F>(mut self, name: &str, value: F) -> Self
+    where
+        F: Fn() -> Option<String> + Send + Sync + 'static,
+    {
+        //! Send the header `name` with every request, with the value returned by `value`.
+        //!
+        //! The closure is called once per operation before the first attempt, so the retries
+        //! of a request share the value. The header is not sent when the closure returns `None`.
+        //! A request fails if the returned value is not a valid header value.
+        //!
+        //! # Example
+        //!
+        //! Tag each request with the trace ID of the current task:
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //!
+        //! tokio::task_local! {
+        //!     static TRACE_ID: String;
+        //! }
+        //!
+        //! let builder = Builder::new()
+        //!     .set_header_fn("x-trace-id", || TRACE_ID.try_with(|trace_id| trace_id.clone()).ok());
+        //! ```
+        self.headers
+            .push((name.to_string(), HeaderValue::Dynamic(Arc::new(value))));
+        self
+    }
+
+    pub fn set_allow_insecure(mut self, allow_insecure: bool) -> Self {
+        //! Allow a plaintext `http://` endpoint.
+        //!
+        //! By default, [Builder::create_client_result] rejects endpoints that are not `https://`
+        //! (except for localhost), because the credentials would be sent insecurely.
+        self.allow_insecure = allow_insecure;
+        self
+    }
+
+    pub fn set_force_path_style(mut self, force_path_style: bool) -> Self {
+        //! Address the bucket in the path (`http://host/bucket/key`) instead of the host name
+        //! (`http://bucket.host/key`).
+        //!
+        //! This is needed for S3 compatible servers without virtual hosted style addressing,
+        //! e.g. MinIO on localhost.
+        //!
+        //! default value of force path style is false.
+        //!
+        //! # Example
+        //!
+        //! A local MinIO server for integration tests:
+        //!
+        //! ```no_run
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //!
+        //! fn main() -> Result<(), Error> {
+        //!     let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!         .set_bucket_name("bucket_name".to_string())
+        //!         .set_access_key_id("minioadmin".to_string())
+        //!         .set_secret_access_key("minioadmin".to_string())
+        //!         .set_endpoint("http://localhost:9000".to_string())
+        //!         .set_region("us-east-1".to_string())
+        //!         .set_force_path_style(true)
+        //!         // not needed for localhost, but needed for e.g. `http://minio:9000` in CI
+        //!         .set_allow_insecure(true)
+        //!         .create_client_result()?;
+        //!     Ok(())
+        //! }
+        //! ```
+        self.force_path_style = force_path_style;
+        self
+    }
+
+    pub fn reset(self) -> Self {
+        //! Reset every setting to its default value, the same as [Builder::new].
+        Self::default()
+    }
+
+    pub fn clear_bucket_name(mut self) -> Self {
+        //! Unset the bucket name.
+        self.bucket_name = None;
+        self
+    }
+
+    pub fn clear_credentials(mut self) -> Self {
+        //! Unset the access key id, the secret access key and the credentials provider.
+        self.access_key_id = None;
+        self.secret_access_key = None;
+        self.credentials_provider = None;
+        self
+    }
+
+    pub fn clear_endpoint(mut self) -> Self {
+        //! Unset the endpoint.
+        self.endpoint = None;
+        self
+    }
+
+    pub fn clear_public_base_url(mut self) -> Self {
+        //! Unset the public base URL.
+        self.public_base_url = None;
+        self
+    }
+
+    pub fn clear_signing_region(mut self) -> Self {
+        //! Unset the signing region, so the region is used for signing.
+        self.signing_region = None;
+        self
+    }
+
+    pub fn clear_retry_classifier(mut self) -> Self {
+        //! Remove the retry classifier set by [Builder::set_retry_classifier].
+        self.retry_classifier = None;
+        self
+    }
+
+    pub fn clear_timeouts(mut self) -> Self {
+        //! Remove the attempt timeout and the operation timeout.
+        self.attempt_timeout = None;
+        self.operation_timeout = None;
+        self
+    }
+
+    pub fn clear_rate_limit(mut self) -> Self {
+        //! Remove the rate limit set by [Builder::set_rate_limit].
+        self.rate_limit = None;
+        self
+    }
+
+    #[cfg(feature = "head-cache")]
+    pub fn clear_head_cache(mut self) -> Self {
+        //! Remove the cache set by [Builder::set_head_cache].
+        self.head_cache = None;
+        self
+    }
+
+    pub fn clear_headers(mut self) -> Self {
+        //! Remove the headers set by [Builder::set_header] and [Builder::set_header_fn].
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //!
+        //! // a template shared by the operators of every bucket
+        //! let template = Builder::new()
+        //!     .set_access_key_id("access_key_id".to_string())
+        //!     .set_secret_access_key("secret_access_key".to_string())
+        //!     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //!     .set_header("x-tenant", "a");
+        //!
+        //! // the same settings without the header of the tenant
+        //! let builder = template
+        //!     .clone()
+        //!     .clear_headers()
+        //!     .set_bucket_name("shared".to_string());
+        //! ```
+        self.headers.clear();
+        self
+    }
+
     #[deprecated(since = "3.1.0", note = "use create_client_result() instead")]
     pub fn create_client(&self) -> Operator {
         //! Create a new [Operator] instance.
@@ -124,39 +607,340 @@ impl Builder {
         )
     }
 
+    pub fn build(&self) -> Result<Operator, BuilderError> {
+        //! Create a new [Operator] instance.
+        //!
+        //! This is an alias of [Builder::create_client_result].
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //!
+        //! fn main() -> Result<(), Error> {
+        //!     let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!         .set_bucket_name("bucket_name".to_string())
+        //!         .set_access_key_id("access_key_id".to_string())
+        //!         .set_secret_access_key("secret_access_key".to_string())
+        //!         .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //!         .build()?;
+        //!     Ok(())
+        //! }
+        //! ```
+        self.create_client_result()
+    }
+
     pub fn create_client_result(&self) -> Result<Operator, BuilderError> {
         //! Create a new [Operator] instance.
+        //!
+        //! Each call creates a new client with its own HTTP connection pool.
+        //! Clone the returned [Operator] to share the pool.
         let bucket_name = match &self.bucket_name {
             Some(bucket_name) => bucket_name.clone(),
             None => Err(BuilderError::BucketNameNotSetError)?,
         };
-        let access_key_id = match &self.access_key_id {
-            Some(access_key_id) => access_key_id,
-            None => Err(BuilderError::AccessKeyIdNotSetError)?,
-        };
-        let secret_access_key = match &self.secret_access_key {
-            Some(secret_access_key) => secret_access_key,
-            None => Err(BuilderError::SecretAccessKeyNotSetError)?,
+        let credentials = match &self.credentials_provider {
+            Some(credentials_provider) => credentials_provider.clone(),
+            None => {
+                let access_key_id = match &self.access_key_id {
+                    Some(access_key_id) => access_key_id,
+                    None => Err(BuilderError::AccessKeyIdNotSetError)?,
+                };
+                let secret_access_key = match &self.secret_access_key {
+                    Some(secret_access_key) => secret_access_key,
+                    None => Err(BuilderError::SecretAccessKeyNotSetError)?,
+                };
+                SharedCredentialsProvider::new(Credentials::new(
+                    access_key_id,
+                    secret_access_key,
+                    None,
+                    None,
+                    "",
+                ))
+            }
         };
         let endpoint = match &self.endpoint {
             Some(endpoint) => endpoint,
             None => Err(BuilderError::EndpointNotSetError)?,
         };
+        if !self.allow_insecure && !is_secure_endpoint(endpoint) {
+            Err(BuilderError::InsecureEndpointError(endpoint.clone()))?;
+        }
 
-        let credentials = Credentials::new(access_key_id, secret_access_key, None, None, "");
+        for (name, value) in &self.headers {
+            // the value of a closure is checked when a request is sent
+            let value = match value {
+                HeaderValue::Static(value) => value.as_str(),
+                HeaderValue::Dynamic(_) => "",
+            };
+            if HttpRequest::empty()
+                .headers_mut()
+                .try_insert(name.clone(), value.to_string())
+                .is_err()
+            {
+                Err(BuilderError::InvalidHeaderError(format!(
+                    "{}: {}",
+                    name, value
+                )))?;
+            }
+        }
 
-        let config = aws_sdk_s3::config::Builder::new()
+        let mut config = aws_sdk_s3::config::Builder::new();
+        if let Some(retry_classifier) = &self.retry_classifier {
+            config = config.retry_classifier(retry_classifier.clone());
+        }
+        if self.retry_signature_errors {
+            config = config.retry_classifier(RetryClassifier(Arc::new(|err: &R2Error| {
+                matches!(
+                    err.code,
+                    Some(S3ErrorCode::SignatureDoesNotMatch | S3ErrorCode::RequestTimeTooSkewed)
+                )
+            })));
+        }
+        if !self.headers.is_empty() {
+            config = config.interceptor(CustomHeaders(self.headers.clone()));
+        }
+        if let Some(signing_region) = &self.signing_region {
+            config = config.interceptor(SigningRegionOverride(signing_region.clone()));
+        }
+        #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+        {
+            config = config
+                .http_client(crate::wasm::FetchHttpClient::new())
+                .time_source(crate::wasm::DateTimeSource)
+                .sleep_impl(crate::wasm::TimeoutSleep);
+        }
+        let config = config
             .credentials_provider(credentials)
             .region(Region::new(self.region.clone()))
             .endpoint_url(endpoint)
+            .force_path_style(self.force_path_style)
             .set_request_checksum_calculation(Some(RequestChecksumCalculation::WhenRequired))
             .set_response_checksum_validation(Some(ResponseChecksumValidation::WhenRequired))
+            .set_retry_config(Some(
+                RetryConfig::standard().with_max_attempts(self.max_attempts),
+            ))
+            .set_timeout_config(self.timeout_config())
             .clone()
             .build();
 
-        Ok(Operator::new(
-            bucket_name,
-            aws_sdk_s3::Client::from_conf(config),
+        let operator = Operator::new(bucket_name, aws_sdk_s3::Client::from_conf(config))
+            .with_max_attempts(self.max_attempts)
+            .with_rate_limit(self.rate_limit)
+            .with_verify_integrity(self.verify_integrity)
+            .with_request_payer(self.request_payer)
+            .with_public_url(Some(endpoint.clone()), self.public_base_url.clone());
+        #[cfg(feature = "head-cache")]
+        let operator = operator.with_head_cache(self.head_cache);
+        Ok(operator)
+    }
+
+    #[cfg(feature = "blocking")]
+    pub fn create_blocking_client_result(&self) -> Result<crate::blocking::Operator, BuilderError> {
+        //! Create a new [blocking::Operator](crate::blocking::Operator) instance.
+        //!
+        //! Requires the `blocking` feature.
+        Ok(crate::blocking::Operator::new(
+            self.create_client_result()?,
+            self.runtime_handle.clone(),
         ))
     }
 }
+
+impl Builder {
+    /// Build the [TimeoutConfig] from the attempt and operation timeouts, if any is set.
+    fn timeout_config(&self) -> Option<TimeoutConfig> {
+        if self.attempt_timeout.is_none() && self.operation_timeout.is_none() {
+            return None;
+        }
+        let mut timeout_config = TimeoutConfig::builder();
+        timeout_config
+            .set_operation_attempt_timeout(self.attempt_timeout)
+            .set_operation_timeout(self.operation_timeout);
+        Some(timeout_config.build())
+    }
+}
+
+/// A closure deciding whether a failed attempt is retried.
+#[derive(Clone)]
+struct RetryClassifier(Arc<dyn Fn(&R2Error) -> bool + Send + Sync>);
+
+impl std::fmt::Debug for RetryClassifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RetryClassifier")
+    }
+}
+
+impl ClassifyRetry for RetryClassifier {
+    fn classify_retry(&self, ctx: &InterceptorContext) -> RetryAction {
+        let error = match ctx.output_or_error() {
+            Some(Err(error)) => error,
+            _ => return RetryAction::NoActionIndicated,
+        };
+        let response = ctx.response();
+        let code = response
+            .and_then(|response| response.body().bytes())
+            .and_then(|body| xml_element(&String::from_utf8_lossy(body), "Code"))
+            .map(|code| S3ErrorCode::from_code(&code));
+        let err = R2Error {
+            code,
+            status: response.map(|response| response.status().as_u16()),
+            message: error.to_string(),
+        };
+        if (self.0)(&err) {
+            RetryAction::transient_error()
+        } else {
+            RetryAction::NoActionIndicated
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "cf-r2-sdk retry classifier"
+    }
+}
+
+/// Value of a header set by [Builder::set_header] or [Builder::set_header_fn].
+#[derive(Clone)]
+enum HeaderValue {
+    Static(String),
+    Dynamic(Arc<dyn Fn() -> Option<String> + Send + Sync>),
+}
+
+impl std::fmt::Debug for HeaderValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Static(value) => f.debug_tuple("Static").field(value).finish(),
+            Self::Dynamic(_) => f.write_str("Dynamic"),
+        }
+    }
+}
+
+/// Interceptor adding the custom headers to every request.
+#[derive(Debug)]
+struct CustomHeaders(Vec<(String, HeaderValue)>);
+
+impl Intercept for CustomHeaders {
+    fn name(&self) -> &'static str {
+        "cf-r2-sdk custom headers"
+    }
+
+    fn modify_before_retry_loop(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), aws_sdk_s3::error::BoxError> {
+        let headers = context.request_mut().headers_mut();
+        for (name, value) in &self.0 {
+            let value = match value {
+                HeaderValue::Static(value) => value.clone(),
+                HeaderValue::Dynamic(value) => match value() {
+                    Some(value) => value,
+                    None => continue,
+                },
+            };
+            headers.try_insert(name.clone(), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Interceptor replacing the SigV4 signing region of the resolved endpoint,
+/// so the region is still used to resolve the endpoint.
+#[derive(Debug)]
+struct SigningRegionOverride(String);
+
+impl SigningRegionOverride {
+    /// Replace the `signingRegion` of an auth scheme `properties` document.
+    fn override_document(&self, properties: Document) -> Document {
+        match properties {
+            Document::Object(mut properties) => {
+                if properties.contains_key("signingRegion") {
+                    properties.insert(
+                        "signingRegion".to_string(),
+                        Document::String(self.0.clone()),
+                    );
+                }
+                Document::Object(properties)
+            }
+            properties => properties,
+        }
+    }
+}
+
+impl Intercept for SigningRegionOverride {
+    fn name(&self) -> &'static str {
+        "cf-r2-sdk signing region override"
+    }
+
+    fn modify_before_signing(
+        &self,
+        _context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), aws_sdk_s3::error::BoxError> {
+        let endpoint = match cfg.load::<Endpoint>() {
+            Some(endpoint) => endpoint,
+            None => return Ok(()),
+        };
+        let mut builder = Endpoint::builder().url(endpoint.url().to_string());
+        for (name, values) in endpoint.headers() {
+            for value in values {
+                builder = builder.header(name.to_string(), value.to_string());
+            }
+        }
+        for (key, value) in endpoint.properties() {
+            // older endpoint resolvers put the auth schemes in the `authSchemes` property
+            let value = match (key.as_ref(), value) {
+                ("authSchemes", Document::Array(schemes)) => Document::Array(
+                    schemes
+                        .iter()
+                        .map(|scheme| self.override_document(scheme.clone()))
+                        .collect(),
+                ),
+                _ => value.clone(),
+            };
+            builder = builder.property(key.clone(), value);
+        }
+        for scheme in endpoint.auth_schemes() {
+            let mut overridden = EndpointAuthScheme::with_capacity(scheme.name().to_string(), 4);
+            if let Document::Object(properties) = self.override_document(scheme.as_document()) {
+                for (key, value) in properties {
+                    if key != "name" {
+                        overridden = overridden.put(key, value);
+                    }
+                }
+            }
+            builder = builder.auth_scheme(overridden);
+        }
+        cfg.interceptor_state().store_put(builder.build());
+        Ok(())
+    }
+}
+
+/// Get the text of the first `<name>` element of `xml`.
+fn xml_element(xml: &str, name: &str) -> Option<String> {
+    let start = xml.find(&format!("<{}>", name))? + name.len() + 2;
+    let end = start + xml[start..].find(&format!("</{}>", name))?;
+    Some(xml[start..end].to_string())
+}
+
+/// Check whether `endpoint` is `https://` or points to localhost.
+pub(crate) fn is_secure_endpoint(endpoint: &str) -> bool {
+    let endpoint = endpoint.to_ascii_lowercase();
+    if endpoint.starts_with("https://") {
+        return true;
+    }
+    match endpoint.strip_prefix("http://") {
+        Some(rest) => {
+            let host = rest.split('/').next().unwrap_or("");
+            let host = match host.strip_prefix('[') {
+                Some(ipv6) => ipv6.split(']').next().unwrap_or(""),
+                None => host.split(':').next().unwrap_or(""),
+            };
+            matches!(host, "localhost" | "127.0.0.1" | "::1")
+        }
+        None => false,
+    }
+}
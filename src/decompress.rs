@@ -0,0 +1,71 @@
+//! Decoding of the `Content-Encoding` of a downloaded body.
+//!
+//! `gzip` (RFC 1952, all members), `deflate` (RFC 1950, or raw RFC 1951 data as sent by some servers)
+//! and `br` (RFC 7932) are decoded with `flate2` and `brotli`.
+
+use flate2::read::{DeflateDecoder, MultiGzDecoder, ZlibDecoder};
+use std::io::Read;
+
+/// Maximum size of a decoded body, so that a small compressed body cannot exhaust the memory.
+pub(crate) const MAX_DECODED_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Decode `body` encoded with `content_encoding` (e.g. `gzip`, or `gzip, deflate`).
+///
+/// The encodings are undone in the reverse order of the header.
+/// An empty or `identity` encoding returns `body` as it is.
+pub(crate) fn decode(
+    content_encoding: &str,
+    body: Vec<u8>,
+) -> Result<Vec<u8>, crate::error::OperationError> {
+    decode_limited(content_encoding, body, MAX_DECODED_SIZE)
+}
+
+/// Same as [decode], but each decoded body may be at most `limit` bytes.
+pub(crate) fn decode_limited(
+    content_encoding: &str,
+    mut body: Vec<u8>,
+    limit: u64,
+) -> Result<Vec<u8>, crate::error::OperationError> {
+    for encoding in content_encoding.rsplit(',') {
+        body = match encoding.trim().to_ascii_lowercase().as_str() {
+            "" | "identity" => body,
+            "gzip" | "x-gzip" => read_limited(MultiGzDecoder::new(body.as_slice()), limit)?,
+            "deflate" if is_zlib(&body) => read_limited(ZlibDecoder::new(body.as_slice()), limit)?,
+            "deflate" => read_limited(DeflateDecoder::new(body.as_slice()), limit)?,
+            "br" => read_limited(brotli::Decompressor::new(body.as_slice(), 4096), limit)?,
+            encoding => {
+                return Err(crate::error::OperationError::UnsupportedEncoding(
+                    encoding.to_string(),
+                ))
+            }
+        };
+    }
+    Ok(body)
+}
+
+/// Read the whole `decoder`, failing if it yields more than `limit` bytes.
+fn read_limited(decoder: impl Read, limit: u64) -> Result<Vec<u8>, crate::error::OperationError> {
+    let mut body = Vec::new();
+    if let Err(err) = decoder.take(limit + 1).read_to_end(&mut body) {
+        return Err(crate::error::OperationError::DecompressError(
+            err.to_string(),
+        ));
+    }
+    if body.len() as u64 > limit {
+        return Err(crate::error::OperationError::DecompressError(format!(
+            "the decoded body exceeds the limit of {} bytes",
+            limit
+        )));
+    }
+    Ok(body)
+}
+
+/// Whether `data` starts with a zlib header (RFC 1950) rather than raw deflate data.
+fn is_zlib(data: &[u8]) -> bool {
+    match data {
+        [cmf, flg, ..] => {
+            cmf & 0x0f == 8 && cmf >> 4 <= 7 && u16::from_be_bytes([*cmf, *flg]) % 31 == 0
+        }
+        _ => false,
+    }
+}
@@ -1,22 +1,111 @@
+use aws_sdk_s3::{
+    config::http::HttpResponse,
+    error::{ProvideErrorMetadata, SdkError},
+};
+
 /// OperationError is an error type that represents the error occurred during the operation.
+///
+/// New variants may be added in minor releases, so a `match` needs a wildcard arm.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum OperationError {
     #[error(transparent)]
     FileOpenError(#[from] std::io::Error),
     #[error("{0}")]
-    AWSSdkS3PutObjectError(String),
+    AWSSdkS3PutObjectError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3GetObjectError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3DeleteObjectError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3DeleteObjectsError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3ListObjectsV2Error(R2Error),
+    #[error("{0}")]
+    AWSSdkS3CopyObjectError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3HeadObjectError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3ListMultipartUploadsError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3AbortMultipartUploadError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3CreateMultipartUploadError(R2Error),
     #[error("{0}")]
-    AWSSdkS3GetObjectError(String),
+    AWSSdkS3UploadPartError(R2Error),
     #[error("{0}")]
-    AWSSdkS3DeleteObjectError(String),
+    AWSSdkS3CompleteMultipartUploadError(R2Error),
     #[error("{0}")]
-    AWSSdkS3ListObjectsV2Error(String),
+    AWSSdkS3GetObjectAclError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3PutObjectAclError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3RestoreObjectError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3GetBucketCorsError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3PutBucketCorsError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3DeleteBucketCorsError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3GetBucketLifecycleConfigurationError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3PutBucketLifecycleConfigurationError(R2Error),
+    #[error("{0}")]
+    AWSSdkS3DeleteBucketLifecycleError(R2Error),
     #[error(transparent)]
     AWSSdkS3ByteStreamError(#[from] aws_sdk_s3::primitives::ByteStreamError),
+    #[error("InvalidRange: bytes {start}-{end} is not a valid range{}.", size.map(|size| format!(" of a file of {} bytes", size)).unwrap_or_default())]
+    InvalidRange {
+        start: u64,
+        end: u64,
+        size: Option<u64>,
+    },
+    #[error("SerializeError: {0}")]
+    SerializeError(String),
+    #[error("DeserializeError: {0}")]
+    DeserializeError(String),
+    #[error("InvalidContentType: {0:?} is not a valid MIME type.")]
+    InvalidContentType(String),
+    #[error("UnsupportedEncoding: Content-Encoding {0} is not supported.")]
+    UnsupportedEncoding(String),
+    #[error("DecompressError: {0}")]
+    DecompressError(String),
+    #[error("IntegrityMismatch: expected {expected}, but got {actual}.")]
+    IntegrityMismatch { expected: String, actual: String },
+    #[error("TooLarge: The file is {size} bytes, which exceeds the limit of {limit} bytes.")]
+    TooLarge { size: u64, limit: u64 },
+    #[error("PreconditionFailed: {0}")]
+    PreconditionFailed(String),
+    #[error("NotSupported: {0}")]
+    NotSupported(String),
+    #[error("Cancelled: The operation was cancelled.")]
+    Cancelled,
+    #[error("Timeout: The operation did not complete within {0:?}.")]
+    Timeout(std::time::Duration),
+    #[error("AuthError: The credentials are invalid or expired. {0}")]
+    AuthError(R2Error),
+    #[error("InvalidKey: {0}")]
+    InvalidKey(String),
+    #[error("PublicUrlNotSet: Set the endpoint or the public base URL with the Builder to build the URL of a file.")]
+    PublicUrlNotSet,
+    #[error("MoveDeleteFailed: {from} was copied to {copied_to}, but could not be deleted, so the object now exists at both keys. {message}")]
+    MoveDeleteFailed {
+        from: String,
+        copied_to: String,
+        message: String,
+    },
+    #[error("Redirect: The request was redirected to {location}. Set the endpoint to the redirected host instead.")]
+    Redirect { location: String },
+    #[error("{0}")]
+    Other(String),
 }
 
 /// BuilderError is an error type that represents the error occurred during the builder process.
+///
+/// New variants may be added in minor releases, so a `match` needs a wildcard arm.
 #[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
 pub enum BuilderError {
     #[error("BucketNameNotSetError: Bucket name is not set.")]
     BucketNameNotSetError,
@@ -26,6 +115,14 @@ pub enum BuilderError {
     SecretAccessKeyNotSetError,
     #[error("EndpointNotSetError: Endpoint is not set.")]
     EndpointNotSetError,
+    #[error("InsecureEndpointError: Endpoint {0} is not https. Use set_allow_insecure(true) to allow it.")]
+    InsecureEndpointError(String),
+    #[error("InvalidAccountIdError: {0:?} is not a valid Cloudflare account id.")]
+    InvalidAccountIdError(String),
+    #[error("InvalidHeaderError: {0:?} is not a valid header name or value.")]
+    InvalidHeaderError(String),
+    #[error("{0}")]
+    Other(String),
 }
 
 /// Error is an error type that represents the error occurred during the operation or the builder process.
@@ -36,3 +133,211 @@ pub enum Error {
     #[error(transparent)]
     BuilderError(#[from] BuilderError),
 }
+
+/// Error code returned by R2 (the S3 compatible error code).
+///
+/// Codes not listed here are kept in [S3ErrorCode::Unknown].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum S3ErrorCode {
+    /// The file (key) does not exist.
+    NoSuchKey,
+    /// The bucket does not exist.
+    NoSuchBucket,
+    /// The multipart upload does not exist.
+    NoSuchUpload,
+    /// The credentials do not have the permission.
+    AccessDenied,
+    /// The access key id does not exist.
+    InvalidAccessKeyId,
+    /// The signature of the request is wrong, e.g. the secret access key is wrong.
+    SignatureDoesNotMatch,
+    /// The session token of the temporary credentials has expired.
+    ExpiredToken,
+    /// The time of the request differs too much from the time of the server, i.e. the clock is skewed.
+    RequestTimeTooSkewed,
+    /// Too many requests. Retry later.
+    SlowDown,
+    /// A precondition (e.g. `If-Match`) failed.
+    PreconditionFailed,
+    /// The requested range cannot be satisfied.
+    InvalidRange,
+    /// The uploaded file is too large.
+    EntityTooLarge,
+    /// An error on the server side.
+    InternalError,
+    /// The operation is not implemented by the server.
+    NotImplemented,
+    /// Any other error code.
+    Unknown(String),
+}
+
+impl S3ErrorCode {
+    pub fn from_code(code: &str) -> Self {
+        //! Convert an error code string (e.g. `"NoSuchKey"`) to [S3ErrorCode].
+        match code {
+            "NoSuchKey" => Self::NoSuchKey,
+            "NoSuchBucket" => Self::NoSuchBucket,
+            "NoSuchUpload" => Self::NoSuchUpload,
+            "AccessDenied" => Self::AccessDenied,
+            "InvalidAccessKeyId" => Self::InvalidAccessKeyId,
+            "SignatureDoesNotMatch" => Self::SignatureDoesNotMatch,
+            "ExpiredToken" => Self::ExpiredToken,
+            "RequestTimeTooSkewed" => Self::RequestTimeTooSkewed,
+            "SlowDown" => Self::SlowDown,
+            "PreconditionFailed" => Self::PreconditionFailed,
+            "InvalidRange" => Self::InvalidRange,
+            "EntityTooLarge" => Self::EntityTooLarge,
+            "InternalError" => Self::InternalError,
+            "NotImplemented" => Self::NotImplemented,
+            code => Self::Unknown(code.to_string()),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        //! Get the error code string (e.g. `"NoSuchKey"`).
+        match self {
+            Self::NoSuchKey => "NoSuchKey",
+            Self::NoSuchBucket => "NoSuchBucket",
+            Self::NoSuchUpload => "NoSuchUpload",
+            Self::AccessDenied => "AccessDenied",
+            Self::InvalidAccessKeyId => "InvalidAccessKeyId",
+            Self::SignatureDoesNotMatch => "SignatureDoesNotMatch",
+            Self::ExpiredToken => "ExpiredToken",
+            Self::RequestTimeTooSkewed => "RequestTimeTooSkewed",
+            Self::SlowDown => "SlowDown",
+            Self::PreconditionFailed => "PreconditionFailed",
+            Self::InvalidRange => "InvalidRange",
+            Self::EntityTooLarge => "EntityTooLarge",
+            Self::InternalError => "InternalError",
+            Self::NotImplemented => "NotImplemented",
+            Self::Unknown(code) => code,
+        }
+    }
+}
+
+impl std::fmt::Display for S3ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// R2Error is an error returned by a request to R2.
+///
+/// `code` and `status` are `None` when no response was received (e.g. a timeout).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{message}")]
+pub struct R2Error {
+    /// Error code of the response.
+    pub code: Option<S3ErrorCode>,
+    /// HTTP status code of the response.
+    pub status: Option<u16>,
+    /// Error message.
+    pub message: String,
+}
+
+impl R2Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        //! Create a new [R2Error] without a response.
+        Self {
+            code: None,
+            status: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl<E: ProvideErrorMetadata + std::error::Error> From<SdkError<E, HttpResponse>> for R2Error {
+    fn from(err: SdkError<E, HttpResponse>) -> Self {
+        Self::from(&err)
+    }
+}
+
+impl<E: ProvideErrorMetadata + std::error::Error> From<&SdkError<E, HttpResponse>> for R2Error {
+    fn from(err: &SdkError<E, HttpResponse>) -> Self {
+        let code = err.code().map(S3ErrorCode::from_code);
+        let message = match (&code, err.message()) {
+            (Some(code), Some(message)) => format!("{}: {}", code, message),
+            (Some(code), None) => code.to_string(),
+            (None, _) => err.to_string(),
+        };
+        Self {
+            code,
+            status: err
+                .raw_response()
+                .map(|response| response.status().as_u16()),
+            message,
+        }
+    }
+}
+
+impl OperationError {
+    pub fn code(&self) -> Option<&S3ErrorCode> {
+        //! Get the error code returned by R2, if this error came from a response of R2.
+        //!
+        //! ```
+        //! use cf_r2_sdk::error::{OperationError, S3ErrorCode};
+        //!
+        //! fn is_not_found(err: &OperationError) -> bool {
+        //!     matches!(err.code(), Some(S3ErrorCode::NoSuchKey))
+        //! }
+        //! ```
+        self.r2_error().and_then(|err| err.code.as_ref())
+    }
+
+    pub fn r2_error(&self) -> Option<&R2Error> {
+        //! Get the [R2Error] (error code, HTTP status and message) of a request to R2, if this error came from one.
+        //!
+        //! ```
+        //! use cf_r2_sdk::error::OperationError;
+        //!
+        //! fn status(err: &OperationError) -> Option<u16> {
+        //!     err.r2_error().and_then(|err| err.status)
+        //! }
+        //! ```
+        match self {
+            Self::AWSSdkS3PutObjectError(err)
+            | Self::AWSSdkS3GetObjectError(err)
+            | Self::AWSSdkS3DeleteObjectError(err)
+            | Self::AWSSdkS3DeleteObjectsError(err)
+            | Self::AWSSdkS3ListObjectsV2Error(err)
+            | Self::AWSSdkS3CopyObjectError(err)
+            | Self::AWSSdkS3HeadObjectError(err)
+            | Self::AWSSdkS3ListMultipartUploadsError(err)
+            | Self::AWSSdkS3AbortMultipartUploadError(err)
+            | Self::AWSSdkS3CreateMultipartUploadError(err)
+            | Self::AWSSdkS3UploadPartError(err)
+            | Self::AWSSdkS3CompleteMultipartUploadError(err)
+            | Self::AWSSdkS3GetObjectAclError(err)
+            | Self::AWSSdkS3PutObjectAclError(err)
+            | Self::AWSSdkS3RestoreObjectError(err)
+            | Self::AWSSdkS3GetBucketCorsError(err)
+            | Self::AWSSdkS3PutBucketCorsError(err)
+            | Self::AWSSdkS3DeleteBucketCorsError(err)
+            | Self::AWSSdkS3GetBucketLifecycleConfigurationError(err)
+            | Self::AWSSdkS3PutBucketLifecycleConfigurationError(err)
+            | Self::AWSSdkS3DeleteBucketLifecycleError(err)
+            | Self::AuthError(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    /// Convert an error caused by invalid or expired credentials to [OperationError::AuthError].
+    pub(crate) fn into_auth_error(self) -> Self {
+        match self.r2_error() {
+            Some(err)
+                if matches!(
+                    err.code,
+                    Some(
+                        S3ErrorCode::InvalidAccessKeyId
+                            | S3ErrorCode::SignatureDoesNotMatch
+                            | S3ErrorCode::ExpiredToken
+                    )
+                ) =>
+            {
+                Self::AuthError(err.clone())
+            }
+            _ => self,
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{error::OperationError, operator::Operator};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Serialization format used by [Operator::upload_serde] and [Operator::download_serde].
+///
+/// Each format is enabled by its own feature (`json`, `toml`, or `msgpack`).
+pub trait Format {
+    /// `Content-Type` of the uploaded file.
+    const CONTENT_TYPE: &'static str;
+
+    /// Serialize `value` into binary data.
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, OperationError>;
+
+    /// Deserialize binary data into a value.
+    fn deserialize<T: DeserializeOwned>(binary: &[u8]) -> Result<T, OperationError>;
+}
+
+/// JSON format (`application/json`). Requires the `json` feature.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, Copy)]
+pub struct Json;
+
+#[cfg(feature = "json")]
+impl Format for Json {
+    const CONTENT_TYPE: &'static str = "application/json";
+
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, OperationError> {
+        serde_json::to_vec(value).map_err(|err| OperationError::SerializeError(err.to_string()))
+    }
+
+    fn deserialize<T: DeserializeOwned>(binary: &[u8]) -> Result<T, OperationError> {
+        serde_json::from_slice(binary)
+            .map_err(|err| OperationError::DeserializeError(err.to_string()))
+    }
+}
+
+/// TOML format (`application/toml`). Requires the `toml` feature.
+#[cfg(feature = "toml")]
+#[derive(Debug, Clone, Copy)]
+pub struct Toml;
+
+#[cfg(feature = "toml")]
+impl Format for Toml {
+    const CONTENT_TYPE: &'static str = "application/toml";
+
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, OperationError> {
+        toml::to_string(value)
+            .map(String::into_bytes)
+            .map_err(|err| OperationError::SerializeError(err.to_string()))
+    }
+
+    fn deserialize<T: DeserializeOwned>(binary: &[u8]) -> Result<T, OperationError> {
+        let text = std::str::from_utf8(binary)
+            .map_err(|err| OperationError::DeserializeError(err.to_string()))?;
+        toml::from_str(text).map_err(|err| OperationError::DeserializeError(err.to_string()))
+    }
+}
+
+/// MessagePack format (`application/msgpack`). Requires the `msgpack` feature.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone, Copy)]
+pub struct MessagePack;
+
+#[cfg(feature = "msgpack")]
+impl Format for MessagePack {
+    const CONTENT_TYPE: &'static str = "application/msgpack";
+
+    fn serialize<T: Serialize>(value: &T) -> Result<Vec<u8>, OperationError> {
+        rmp_serde::to_vec_named(value)
+            .map_err(|err| OperationError::SerializeError(err.to_string()))
+    }
+
+    fn deserialize<T: DeserializeOwned>(binary: &[u8]) -> Result<T, OperationError> {
+        rmp_serde::from_slice(binary)
+            .map_err(|err| OperationError::DeserializeError(err.to_string()))
+    }
+}
+
+impl Operator {
+    pub async fn upload_serde<T: Serialize, F: Format>(
+        &self,
+        file_name: &str,
+        value: &T,
+        cache_control: Option<&str>,
+    ) -> Result<(), OperationError> {
+        //! Serialize `value` with the format `F` and upload it to the R2 bucket.
+        //!
+        //! The `Content-Type` is set from [Format::CONTENT_TYPE].
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::format::Json;
+        //! use serde::{Deserialize, Serialize};
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        //!    struct Config {
+        //!        name: String,
+        //!        retries: u32,
+        //!    }
+        //!    let config = Config { name: "r2".to_string(), retries: 3 };
+        //!
+        //!    // upload as JSON
+        //!    object
+        //!        .upload_serde::<_, Json>("doctest_format_upload_serde.json", &config, None)
+        //!        .await?;
+        //!
+        //!    // download as JSON
+        //!    let downloaded: Config = object
+        //!        .download_serde::<_, Json>("doctest_format_upload_serde.json")
+        //!        .await?;
+        //!    assert_eq!(downloaded, config);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_format_upload_serde.json").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_serde", async {
+            let binary = F::serialize(value)?;
+            self.upload_binary(file_name, F::CONTENT_TYPE, &binary, cache_control)
+                .await
+        })
+        .await
+    }
+
+    pub async fn download_serde<T: DeserializeOwned, F: Format>(
+        &self,
+        file_name: &str,
+    ) -> Result<T, OperationError> {
+        //! Download a file from the R2 bucket and deserialize it with the format `F`.
+        //!
+        //! See [Operator::upload_serde] for an example.
+        self.instrument("download_serde", async {
+            let binary = self.download(file_name).await?;
+            F::deserialize(&binary)
+        })
+        .await
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{
+    error::OperationError,
+    object::ObjectMetadata,
+    operator::{object_metadata, quote_etag, status_code, Operator},
+};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Least recently used cache of `HeadObject` results, shared by the clones of an [Operator].
+#[derive(Debug)]
+pub(crate) struct HeadCache {
+    capacity: usize,
+    max_age: Duration,
+    entries: Mutex<Entries>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    /// Metadata keyed by bucket name and file name.
+    map: HashMap<(String, String), Entry>,
+    /// Incremented on each access to order the entries by their last use.
+    clock: u64,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    metadata: ObjectMetadata,
+    validated: Instant,
+    used: u64,
+}
+
+impl HeadCache {
+    pub(crate) fn new(capacity: usize, max_age: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            max_age,
+            entries: Mutex::new(Entries::default()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        match self.entries.lock() {
+            Ok(entries) => entries,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Get the cached metadata and whether it is still fresh at `now`.
+    fn get(&self, key: &(String, String), now: Instant) -> Option<(ObjectMetadata, bool)> {
+        let mut entries = self.lock();
+        entries.clock += 1;
+        let clock = entries.clock;
+        let entry = entries.map.get_mut(key)?;
+        entry.used = clock;
+        let fresh = now.duration_since(entry.validated) < self.max_age;
+        Some((entry.metadata.clone(), fresh))
+    }
+
+    /// Insert or replace the metadata, evicting the least recently used entry when full.
+    fn insert(&self, key: (String, String), metadata: ObjectMetadata, now: Instant) {
+        let mut entries = self.lock();
+        entries.clock += 1;
+        let used = entries.clock;
+        if !entries.map.contains_key(&key) && entries.map.len() >= self.capacity {
+            let oldest = entries
+                .map
+                .iter()
+                .min_by_key(|(_, entry)| entry.used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.map.remove(&oldest);
+            }
+        }
+        entries.map.insert(
+            key,
+            Entry {
+                metadata,
+                validated: now,
+                used,
+            },
+        );
+    }
+
+    fn remove(&self, key: &(String, String)) {
+        self.lock().map.remove(key);
+    }
+}
+
+impl Operator {
+    pub async fn head_object_cached(
+        &self,
+        file_name: &str,
+    ) -> Result<ObjectMetadata, OperationError> {
+        //! Get the metadata of a file in the R2 bucket, using the cache set by
+        //! [Builder::set_head_cache](crate::builder::Builder::set_head_cache).
+        //!
+        //! Metadata younger than the max age is returned without a request.
+        //! Older metadata is revalidated with `If-None-Match: <ETag>`, so a `304 Not Modified`
+        //! keeps the cached metadata and a changed file replaces it.
+        //! A file that no longer exists is removed from the cache.
+        //! Without a cache, this is the same as [Operator::head_object].
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! # use std::time::Duration;
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! let object = Builder::new()
+        //!     .set_bucket_name("bucket_name".to_string())
+        //!     .set_access_key_id("access_key_id".to_string())
+        //!     .set_secret_access_key("secret_access_key".to_string())
+        //!     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //!     // keep up to 1000 files, revalidated after 60 seconds
+        //!     .set_head_cache(1000, Duration::from_secs(60))
+        //!     .create_client_result()?;
+        //!
+        //! // only the first call sends a request within 60 seconds
+        //! for _ in 0..3 {
+        //!     let metadata = object.head_object_cached("index.html").await?;
+        //!     println!("Content-Length: {}", metadata.size);
+        //! }
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("head_object_cached", async {
+            let cache = match &self.head_cache {
+                Some(cache) => cache,
+                None => return self.head_object(file_name).await,
+            };
+            let key = (self.bucket_name.clone(), file_name.to_string());
+            let cached = cache.get(&key, Instant::now());
+            let e_tag = match cached {
+                Some((metadata, true)) => return Ok(metadata),
+                Some((metadata, false)) => metadata.e_tag.as_deref().map(quote_etag),
+                None => None,
+            };
+
+            let result = self
+                .client
+                .head_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .set_if_none_match(e_tag)
+                .send()
+                .await;
+            match result {
+                Ok(output) => {
+                    let metadata = object_metadata(&output);
+                    cache.insert(key, metadata.clone(), Instant::now());
+                    Ok(metadata)
+                }
+                Err(err) if status_code(&err) == Some(304) => {
+                    match cache.get(&key, Instant::now()) {
+                        Some((metadata, _)) => {
+                            cache.insert(key, metadata.clone(), Instant::now());
+                            Ok(metadata)
+                        }
+                        // evicted while revalidating
+                        None => self.head_object(file_name).await,
+                    }
+                }
+                Err(err) => {
+                    if status_code(&err) == Some(404) {
+                        cache.remove(&key);
+                    }
+                    Err(OperationError::AWSSdkS3HeadObjectError(err.into()))
+                }
+            }
+        })
+        .await
+    }
+
+    pub fn invalidate_head_cache(&self, file_name: &str) {
+        //! Remove the cached metadata of a file, e.g. after it was overwritten or deleted.
+        if let Some(cache) = &self.head_cache {
+            cache.remove(&(self.bucket_name.clone(), file_name.to_string()));
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{error::OperationError, operator::Operator};
+use std::future::Future;
+
+tokio::task_local! {
+    /// Set while an [Operator] method runs, so the methods it calls are not recorded again.
+    static INSTRUMENTED: ();
+}
+
+/// Run an [Operator](crate::operator::Operator) method and record its metrics.
+///
+/// Errors caused by invalid or expired credentials are converted to [OperationError::AuthError].
+///
+/// With the `metrics` feature, `r2_operations_total{op, result}` is incremented and
+/// the elapsed seconds are recorded to `r2_operation_duration_seconds{op}`.
+/// Without the feature, this only awaits `operation`.
+pub(crate) async fn instrument<T, F>(op: &'static str, operation: F) -> Result<T, OperationError>
+where
+    F: Future<Output = Result<T, OperationError>>,
+{
+    #[cfg(feature = "metrics")]
+    {
+        let start = std::time::Instant::now();
+        let result = operation.await.map_err(OperationError::into_auth_error);
+        let outcome = if result.is_ok() { "ok" } else { "error" };
+        metrics::counter!("r2_operations_total", "op" => op, "result" => outcome).increment(1);
+        metrics::histogram!("r2_operation_duration_seconds", "op" => op)
+            .record(start.elapsed().as_secs_f64());
+        result
+    }
+    #[cfg(not(feature = "metrics"))]
+    {
+        let _ = op;
+        operation.await.map_err(OperationError::into_auth_error)
+    }
+}
+
+impl Operator {
+    /// Run an [Operator] method with the deadline set by [Operator::with_timeout], and record its metrics.
+    ///
+    /// A method called by another method (e.g. [Operator::download_from] by [Operator::download])
+    /// is part of the outer operation, so it is only awaited and one call is recorded once.
+    pub(crate) async fn instrument<T, F>(
+        &self,
+        op: &'static str,
+        operation: F,
+    ) -> Result<T, OperationError>
+    where
+        F: Future<Output = Result<T, OperationError>>,
+    {
+        // boxed so that the futures of the methods calling other methods stay small
+        let operation = Box::pin(operation);
+        if INSTRUMENTED.try_with(|_| ()).is_ok() {
+            return operation.await;
+        }
+        INSTRUMENTED
+            .scope(
+                (),
+                instrument(op, async {
+                    match self.timeout {
+                        Some(timeout) => match tokio::time::timeout(timeout, operation).await {
+                            Ok(result) => result,
+                            Err(_) => Err(OperationError::Timeout(timeout)),
+                        },
+                        None => operation.await,
+                    }
+                }),
+            )
+            .await
+    }
+}
@@ -284,9 +284,28 @@
 //! }
 //! ```
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod bucket;
 pub mod builder;
+#[cfg(feature = "decompress")]
+mod decompress;
 pub mod error;
+#[cfg(feature = "serde")]
+pub mod format;
+#[cfg(feature = "head-cache")]
+mod head_cache;
+mod instrument;
+pub mod multipart;
+pub mod object;
 pub mod operator;
+pub mod options;
+pub mod prelude;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod sync;
+mod throttle;
+#[cfg(all(feature = "wasm", any(target_arch = "wasm32", test)))]
+mod wasm;
 
 #[cfg(test)]
 mod tests {
@@ -441,10 +460,4451 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn local_test_5_get_range_stream() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("range.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let mut reader = object.get_range_stream("range.txt", 7, 5).await?;
+        let mut buffer = Vec::new();
+        reader
+            .read_to_end(&mut buffer)
+            .await
+            .expect("Failed to read range");
+        assert_eq!(buffer, b"World");
+
+        object.delete("range.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn local_test_6_delete_objects() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("delete_1.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+        object
+            .upload_binary("delete_2.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let mut result = object
+            .delete_objects(&["delete_1.txt", "delete_2.txt"])
+            .await?;
+        result.deleted.sort();
+        assert_eq!(result.deleted, vec!["delete_1.txt", "delete_2.txt"]);
+        assert!(result.errors.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn unit_test_1_attach_content_md5() {
+        let mut request = aws_sdk_s3::config::http::HttpRequest::new(
+            aws_sdk_s3::primitives::SdkBody::from("Hello, World!"),
+        );
+        operator::attach_content_md5(&mut request);
+        assert_eq!(
+            request.headers().get("Content-MD5"),
+            Some("ZajifYh5KDgxtmS9i38K1A==")
+        );
+    }
+
+    #[test]
+    fn unit_test_2_join_key() {
+        assert_eq!(sync::join_key("", "a/b.txt"), "a/b.txt");
+        assert_eq!(sync::join_key("backup", "a/b.txt"), "backup/a/b.txt");
+        assert_eq!(sync::join_key("backup/", "a/b.txt"), "backup/a/b.txt");
+
+        assert_eq!(sync::relative_key("", "a/b.txt"), Some("a/b.txt"));
+        assert_eq!(
+            sync::relative_key("backup", "backup/a/b.txt"),
+            Some("a/b.txt")
+        );
+        assert_eq!(
+            sync::relative_key("backup/", "backup/a/b.txt"),
+            Some("a/b.txt")
+        );
+        assert_eq!(sync::relative_key("backup", "backup2/a/b.txt"), None);
+        assert_eq!(sync::relative_key("backup", "backupa/b.txt"), None);
+    }
+
+    #[test]
+    fn unit_test_3_copy_source() {
+        assert_eq!(
+            operator::copy_source("bucket", "folder/a b+c.txt"),
+            "bucket/folder/a%20b%2Bc.txt"
+        );
+        assert_eq!(
+            operator::copy_source("bucket", "名前.txt"),
+            "bucket/%E5%90%8D%E5%89%8D.txt"
+        );
+    }
+
+    #[tokio::test]
+    async fn local_test_7_move_object() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("move.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        object.move_object("move.txt", "moved.txt").await?;
+
+        assert!(object.download("move.txt").await.is_err());
+        assert_eq!(object.download("moved.txt").await?, b"Hello, World!");
+
+        object.delete("moved.txt").await?;
+        Ok(())
+    }
+
+    #[test]
+    fn unit_test_4_transfer_stats() {
+        let stats = object::TransferStats::new(1000, std::time::Duration::from_millis(500));
+        assert_eq!(stats.throughput_bytes_per_sec, 2000.0);
+
+        let stats = object::TransferStats::new(1000, std::time::Duration::ZERO);
+        assert_eq!(stats.throughput_bytes_per_sec, 0.0);
+    }
+
+    #[test]
+    fn unit_test_5_insecure_endpoint() {
+        assert!(builder::is_secure_endpoint(
+            "https://example.r2.cloudflarestorage.com"
+        ));
+        assert!(builder::is_secure_endpoint("http://localhost:9000"));
+        assert!(builder::is_secure_endpoint("http://127.0.0.1:9000"));
+        assert!(builder::is_secure_endpoint("http://[::1]:9000"));
+        assert!(!builder::is_secure_endpoint(
+            "http://example.r2.cloudflarestorage.com"
+        ));
+        assert!(!builder::is_secure_endpoint("http://localhost.example.com"));
+
+        let result = Builder::new()
+            .set_bucket_name("bucket_name".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint("http://example.com".to_string())
+            .create_client_result();
+        assert!(matches!(
+            result,
+            Err(error::BuilderError::InsecureEndpointError(_))
+        ));
+
+        let result = Builder::new()
+            .set_bucket_name("bucket_name".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint("http://example.com".to_string())
+            .set_allow_insecure(true)
+            .create_client_result();
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn local_test_8_download_range() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("download_range.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let result = object
+            .download_range("download_range.txt", Some((7, 11)))
+            .await?;
+        assert!(result.is_partial);
+        assert_eq!(result.bytes, b"World");
+
+        let result = object.download_range("download_range.txt", None).await?;
+        assert!(!result.is_partial);
+        assert_eq!(result.bytes, b"Hello, World!");
+
+        object.delete("download_range.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn local_test_9_special_character_keys() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let file_name = "folder/名前 (1)+copy.txt";
+        let copied_file_name = "folder/名前 (2)+copy.txt";
+        object
+            .upload_binary(file_name, "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let file_names = object.list_objects().await?;
+        assert!(file_names.iter().any(|name| name == file_name));
+
+        assert_eq!(object.download(file_name).await?, b"Hello, World!");
+
+        let copied = object.copy_object(file_name, copied_file_name).await?;
+        assert_eq!(copied.size, 13);
+        assert_eq!(
+            copied.e_tag,
+            object.head_object(copied_file_name).await?.e_tag
+        );
+        assert_eq!(object.download(copied_file_name).await?, b"Hello, World!");
+
+        object.delete(file_name).await?;
+        object.delete(copied_file_name).await?;
+        assert!(object.download(file_name).await.is_err());
+        Ok(())
+    }
+
+    #[cfg(all(feature = "json", feature = "toml", feature = "msgpack"))]
+    #[test]
+    fn unit_test_6_format_round_trip() -> Result<(), Error> {
+        use format::{Format, Json, MessagePack, Toml};
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Config {
+            name: String,
+            retries: u32,
+        }
+        let config = Config {
+            name: "r2".to_string(),
+            retries: 3,
+        };
+
+        let decoded: Config = Json::deserialize(&Json::serialize(&config)?)?;
+        assert_eq!(decoded, config);
+        let decoded: Config = Toml::deserialize(&Toml::serialize(&config)?)?;
+        assert_eq!(decoded, config);
+        let decoded: Config = MessagePack::deserialize(&MessagePack::serialize(&config)?)?;
+        assert_eq!(decoded, config);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn local_test_10_head_object() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("head_object.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let metadata = object.head_object("head_object.txt").await?;
+        assert_eq!(metadata.size, 13);
+        assert_eq!(metadata.content_type.as_deref(), Some("text/plain"));
+        assert_eq!(metadata.cache_control.as_deref(), Some("no-cache"));
+
+        object.delete("head_object.txt").await?;
+        Ok(())
+    }
+
+    #[test]
+    fn unit_test_7_max_attempts() {
+        let object = Builder::new()
+            .set_bucket_name("bucket_name".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+            .set_max_attempts(0)
+            .create_client_result()
+            .unwrap();
+        assert_eq!(object.max_attempts, 1);
+        assert_eq!(object.with_bucket("other").max_attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn local_test_11_website_redirect_location() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let options = options::UploadOptions::new().set_website_redirect_location("/index.html");
+        object
+            .upload_file_streaming(
+                "website_redirect_location.jpg",
+                "image/jpeg",
+                "data/sample.jpg",
+                &options,
+            )
+            .await?;
+
+        let metadata = object.head_object("website_redirect_location.jpg").await?;
+        assert_eq!(
+            metadata.website_redirect_location.as_deref(),
+            Some("/index.html")
+        );
+
+        object.delete("website_redirect_location.jpg").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn local_test_12_dry_run() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("dry_run/a.txt", "text/plain", b"a", None)
+            .await?;
+        object
+            .upload_binary("dry_run/b.txt", "text/plain", b"b", None)
+            .await?;
+
+        let mut planned = object.plan_delete_prefix("dry_run/").await?;
+        planned.sort();
+        assert_eq!(planned, vec!["dry_run/a.txt", "dry_run/b.txt"]);
+        // nothing is deleted by the plan
+        assert_eq!(object.download("dry_run/a.txt").await?, b"a");
+
+        assert_eq!(object.delete_prefix("dry_run/").await?, 2);
+        assert!(object.plan_delete_prefix("dry_run/").await?.is_empty());
+        Ok(())
+    }
+
+    #[cfg(feature = "infer")]
+    #[test]
+    fn unit_test_8_detect_content_type() {
+        assert_eq!(
+            operator::detect_content_type(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR"),
+            "image/png"
+        );
+        assert_eq!(
+            operator::detect_content_type(b"%PDF-1.7"),
+            "application/pdf"
+        );
+        assert_eq!(
+            operator::detect_content_type(b"Hello, World!"),
+            "application/octet-stream"
+        );
+    }
+
+    #[tokio::test]
+    async fn local_test_13_list_objects_limited() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        for i in 0..3 {
+            object
+                .upload_binary(&format!("limited/{}.txt", i), "text/plain", b"a", None)
+                .await?;
+        }
+
+        let list = object.list_objects_limited(2).await?;
+        assert_eq!(list.file_names.len(), 2);
+        assert!(list.has_more);
+
+        for i in 0..3 {
+            object.delete(&format!("limited/{}.txt", i)).await?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unit_test_9_send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        fn assert_send<T: Send>(_: &T) {}
+        assert_send_sync::<operator::Operator>();
+        assert_send_sync::<Builder>();
+        assert_send_sync::<error::Error>();
+
+        let object = Builder::new()
+            .set_bucket_name("bucket_name".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+            .create_client_result()
+            .unwrap();
+        assert_send(&object.upload_binary("file_name", "text/plain", b"", None));
+        assert_send(&object.download("file_name"));
+        assert_send(&object.delete("file_name"));
+        assert_send(&object.list_objects());
+        assert_send(&object.upload_files(&[("file_name", "file_path", "text/plain")]));
+        assert_send(&object.delete_objects(&["file_name"]));
+        assert_send(&object.delete_all());
+        assert_send(&object.sync_up("local_dir", "prefix"));
+        assert_send(&object.head_objects(&["file_name"]));
+        assert_send(&object.upload_reader("file_name", "text/plain", &b""[..], None));
+        assert_send(&object.upload_stdin("file_name", "text/plain", None));
+        assert_send(&object.download_ranges("file_name", &[(0, 1)]));
+    }
+
+    #[test]
+    fn unit_test_10_part_size() {
+        const MIB: usize = 1024 * 1024;
+        assert_eq!(multipart::part_size(0), 64 * MIB);
+        assert_eq!(multipart::part_size(6 * 1024 * MIB), 64 * MIB);
+        // 10,000 parts of 64 MiB are not enough for 1 TiB
+        let len = 1024 * 1024 * MIB;
+        assert!(multipart::part_size(len) * 10_000 >= len);
+    }
+
+    #[tokio::test]
+    async fn local_test_14_head_objects() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("head_objects.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let results = object
+            .head_objects(&["head_objects.txt", "head_objects_missing.txt"])
+            .await?;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "head_objects.txt");
+        assert_eq!(results[0].1.as_ref().unwrap().size, 13);
+        assert_eq!(results[1].0, "head_objects_missing.txt");
+        assert!(results[1].1.is_err());
+
+        object.delete("head_objects.txt").await?;
+        Ok(())
+    }
+
+    #[test]
+    fn unit_test_11_s3_error_code() {
+        use error::{OperationError, R2Error, S3ErrorCode};
+
+        assert_eq!(S3ErrorCode::from_code("NoSuchKey"), S3ErrorCode::NoSuchKey);
+        assert_eq!(S3ErrorCode::from_code("SlowDown").as_str(), "SlowDown");
+        assert_eq!(
+            S3ErrorCode::from_code("TooManyBuckets"),
+            S3ErrorCode::Unknown("TooManyBuckets".to_string())
+        );
+
+        let err = OperationError::AWSSdkS3GetObjectError(R2Error {
+            code: Some(S3ErrorCode::NoSuchKey),
+            status: Some(404),
+            message: "NoSuchKey: The specified key does not exist.".to_string(),
+        });
+        assert!(matches!(err.code(), Some(S3ErrorCode::NoSuchKey)));
+        assert_eq!(
+            err.to_string(),
+            "NoSuchKey: The specified key does not exist."
+        );
+        assert_eq!(OperationError::Cancelled.code(), None);
+    }
+
+    #[tokio::test]
+    async fn local_test_15_copy_object_with_options() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary(
+                "copy_with_options.txt",
+                "text/plain",
+                b"Hello, World!",
+                None,
+            )
+            .await?;
+
+        let options = options::UploadOptions::new().set_cache_control("max-age=31536000");
+        object
+            .copy_object_with_options(
+                "copy_with_options.txt",
+                "copy_with_options_copied.txt",
+                &options,
+            )
+            .await?;
+
+        let metadata = object.head_object("copy_with_options_copied.txt").await?;
+        assert_eq!(metadata.cache_control.as_deref(), Some("max-age=31536000"));
+        assert_eq!(metadata.content_type.as_deref(), Some("text/plain"));
+
+        object.delete("copy_with_options.txt").await?;
+        object.delete("copy_with_options_copied.txt").await?;
+        Ok(())
+    }
+
+    #[test]
+    fn unit_test_12_rate_limiter() {
+        use std::time::{Duration, Instant};
+
+        let rate_limiter = throttle::RateLimiter::new(100);
+        let now = Instant::now();
+        assert_eq!(rate_limiter.reserve(100, now), now);
+        // waits for the 100 bytes reserved before
+        assert_eq!(rate_limiter.reserve(50, now), now + Duration::from_secs(1));
+        assert_eq!(
+            rate_limiter.reserve(10, now),
+            now + Duration::from_millis(1500)
+        );
+        // no wait after the reserved bytes were transferred
+        let later = now + Duration::from_secs(10);
+        assert_eq!(rate_limiter.reserve(10, later), later);
+    }
+
+    #[tokio::test]
+    async fn local_test_16_list_level() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("list_level/sub/a.txt", "text/plain", b"a", None)
+            .await?;
+        object
+            .upload_binary("list_level/b.txt", "text/plain", b"bb", None)
+            .await?;
+
+        let level = object.list_level("list_level/").await?;
+        assert_eq!(level.folders, vec!["list_level/sub/"]);
+        assert_eq!(level.files.len(), 1);
+        assert_eq!(level.files[0].key, "list_level/b.txt");
+        assert_eq!(level.files[0].size, 2);
+
+        object.delete("list_level/sub/a.txt").await?;
+        object.delete("list_level/b.txt").await?;
+        Ok(())
+    }
+
+    #[test]
+    fn unit_test_13_local_path() {
+        use std::path::Path;
+
+        let root = Path::new("backup");
+        assert_eq!(
+            sync::local_path(root, "a/b.txt"),
+            Some(root.join("a").join("b.txt"))
+        );
+        assert_eq!(sync::local_path(root, ""), None);
+        assert_eq!(sync::local_path(root, "a/"), None);
+        assert_eq!(sync::local_path(root, "../b.txt"), None);
+        assert_eq!(sync::local_path(root, "/etc/passwd"), None);
+    }
+
+    #[tokio::test]
+    async fn local_test_17_sync_down() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("sync_down/a.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+        let last_modified = object.head_object("sync_down/a.txt").await?.last_modified;
+
+        let local_dir = env::temp_dir().join("cf_r2_sdk_sync_down");
+        let options = options::SyncDownOptions::new().set_preserve_mtime(true);
+        let report = object.sync_down("sync_down", &local_dir, &options).await?;
+        assert_eq!(report.downloaded, 1);
+
+        let path = local_dir.join("a.txt");
+        assert_eq!(std::fs::read(&path).unwrap(), b"Hello, World!");
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().modified().ok(),
+            last_modified
+        );
+
+        // the second sync skips the up-to-date file
+        let report = object.sync_down("sync_down", &local_dir, &options).await?;
+        assert_eq!(report.skipped, 1);
+
+        std::fs::remove_dir_all(&local_dir).unwrap();
+        object.delete("sync_down/a.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn local_test_18_upload_reader() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let reader: &[u8] = b"Hello, World!";
+        object
+            .upload_reader("upload_reader.txt", "text/plain", reader, None)
+            .await?;
+
+        let bin = object.download("upload_reader.txt").await?;
+        assert_eq!(bin, b"Hello, World!");
+
+        object.delete("upload_reader.txt").await?;
+        Ok(())
+    }
+
+    #[test]
+    fn unit_test_14_etag() {
+        use operator::{check_etag, md5_digest, md5_etag, multipart_etag};
+
+        assert_eq!(
+            md5_etag(&md5_digest(b"")),
+            "\"d41d8cd98f00b204e9800998ecf8427e\""
+        );
+        assert_eq!(
+            multipart_etag(&[md5_digest(b"a"), md5_digest(b"b")]),
+            "\"96e024ba2074fe77e8e965ba43a704be-2\""
+        );
+
+        let expected = Some(md5_etag(&md5_digest(b"")));
+        assert!(check_etag(
+            expected.clone(),
+            Some("\"D41D8CD98F00B204E9800998ECF8427E\"")
+        )
+        .is_ok());
+        assert!(check_etag(None, Some("\"anything\"")).is_ok());
+        match check_etag(expected, Some("\"0cc175b9c0f1b6a831c399e269772661\"")) {
+            Err(error::OperationError::IntegrityMismatch { expected, actual }) => {
+                assert_eq!(expected, "d41d8cd98f00b204e9800998ecf8427e");
+                assert_eq!(actual, "0cc175b9c0f1b6a831c399e269772661");
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn local_test_19_list_objects_page() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        for i in 0..3 {
+            object
+                .upload_binary(&format!("list_page/{}.txt", i), "text/plain", b"a", None)
+                .await?;
+        }
+
+        let mut keys = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let page = object.list_objects_page(1, token.as_deref()).await?;
+            assert_eq!(page.key_count, page.keys.len());
+            keys.extend(page.keys);
+            if !page.is_truncated {
+                break;
+            }
+            token = page.next_token;
+        }
+        for i in 0..3 {
+            assert!(keys.contains(&format!("list_page/{}.txt", i)));
+        }
+
+        for i in 0..3 {
+            object.delete(&format!("list_page/{}.txt", i)).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn local_test_20_append() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object.append("append.log", b"first\n").await?;
+        object.append("append.log", b"second\n").await?;
+
+        let bin = object.download("append.log").await?;
+        assert_eq!(bin, b"first\nsecond\n");
+
+        object.delete("append.log").await?;
+        Ok(())
+    }
+
+    #[test]
+    fn unit_test_15_timeouts() {
+        use std::time::Duration;
+
+        let object = Builder::new()
+            .set_bucket_name("bucket_name".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+            .set_attempt_timeout(Duration::from_secs(5))
+            .set_operation_timeout(Duration::from_secs(60))
+            .create_client_result()
+            .unwrap();
+        let timeout_config = object.client.config().timeout_config().unwrap();
+        assert_eq!(
+            timeout_config.operation_attempt_timeout(),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(
+            timeout_config.operation_timeout(),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[tokio::test]
+    async fn local_test_21_download_capped() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("download_capped.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let bin = object.download_capped("download_capped.txt", 13).await?;
+        assert_eq!(bin, b"Hello, World!");
+        assert!(matches!(
+            object.download_capped("download_capped.txt", 12).await,
+            Err(error::OperationError::TooLarge {
+                size: 13,
+                limit: 12
+            })
+        ));
+
+        object.delete("download_capped.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn local_test_22_upload_binary_idempotent() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary_idempotent("upload_idempotent.txt", "text/plain", b"Hello", None)
+            .await?;
+        // retrying with the same content succeeds
+        object
+            .upload_binary_idempotent("upload_idempotent.txt", "text/plain", b"Hello", None)
+            .await?;
+        // different content does not overwrite the file
+        assert!(matches!(
+            object
+                .upload_binary_idempotent("upload_idempotent.txt", "text/plain", b"World", None)
+                .await,
+            Err(error::OperationError::PreconditionFailed(_))
+        ));
+        assert_eq!(object.download("upload_idempotent.txt").await?, b"Hello");
+
+        object.delete("upload_idempotent.txt").await?;
+        Ok(())
+    }
+
+    #[test]
+    fn unit_test_16_not_supported() {
+        use error::{R2Error, S3ErrorCode};
+
+        let not_implemented = R2Error {
+            code: Some(S3ErrorCode::NotImplemented),
+            status: Some(501),
+            message: "NotImplemented: GetObjectAcl not implemented".to_string(),
+        };
+        assert_eq!(
+            operator::not_supported(not_implemented),
+            Ok("NotImplemented: GetObjectAcl not implemented".to_string())
+        );
+
+        let access_denied = R2Error {
+            code: Some(S3ErrorCode::AccessDenied),
+            status: Some(403),
+            message: "AccessDenied: Access Denied".to_string(),
+        };
+        assert_eq!(
+            operator::not_supported(access_denied.clone()),
+            Err(access_denied)
+        );
+        assert_eq!(
+            S3ErrorCode::from_code("NotImplemented"),
+            S3ErrorCode::NotImplemented
+        );
+    }
+
+    /// Serve `body` as the `200 OK` XML response of every request on a local port.
+    ///
+    /// Return the endpoint and the headers of the received requests.
+    async fn serve_xml(
+        body: &'static str,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        serve_response("200 OK", body).await
+    }
+
+    /// Serve `body` as the XML response with `status` (e.g. `404 Not Found`) of every request on a local port.
+    ///
+    /// Return the endpoint and the headers of the received requests.
+    async fn serve_response(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        serve_with_headers(status, "", body).await
+    }
+
+    /// Serve `body` as the XML response with `status` and the extra `headers`
+    /// (each followed by `\r\n`, e.g. `ETag: "abc"\r\n`) of every request on a local port.
+    ///
+    /// Return the endpoint and the headers of the received requests.
+    async fn serve_with_headers(
+        status: &'static str,
+        headers: &'static str,
+        body: &'static str,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        serve_sequence(vec![(status, headers, body)]).await
+    }
+
+    /// Serve the `(status, headers, body)` responses in order, one per request, on a local port.
+    /// The last response is repeated for the requests after it.
+    ///
+    /// Return the endpoint and the headers of the received requests.
+    async fn serve_sequence(
+        responses: Vec<(&'static str, &'static str, &'static str)>,
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        use tokio::io::AsyncWriteExt;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let received = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                // read the request headers (the requests have no body)
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buffer[..n]),
+                    }
+                }
+                let count = {
+                    let mut received = received.lock().unwrap();
+                    received.push(String::from_utf8_lossy(&request).into_owned());
+                    received.len()
+                };
+                let (status, headers, body) = responses[count.min(responses.len()) - 1];
+                let response = format!(
+                    "HTTP/1.1 {}\r\n{}Content-Type: application/xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    headers,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        (endpoint, requests)
+    }
+
+    /// Create an [operator::Operator] sending requests to `endpoint` with dummy credentials.
+    fn local_operator(endpoint: &str) -> operator::Operator {
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version_latest()
+            .endpoint_url(endpoint)
+            .region(aws_sdk_s3::config::Region::new("auto"))
+            .credentials_provider(aws_sdk_s3::config::Credentials::new(
+                "access_key_id",
+                "secret_access_key",
+                None,
+                None,
+                "test",
+            ))
+            .force_path_style(true)
+            .build();
+        operator::Operator::new("bucket".to_string(), aws_sdk_s3::Client::from_conf(config))
+    }
+
+    #[tokio::test]
+    async fn unit_test_17_list_objects_empty_bucket() {
+        // an empty bucket returns no <Contents> element
+        let (endpoint, _) = serve_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><Prefix></Prefix><MaxKeys>1000</MaxKeys><KeyCount>0</KeyCount><IsTruncated>false</IsTruncated></ListBucketResult>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+
+        assert_eq!(object.list_objects().await.unwrap(), Vec::<String>::new());
+        assert_eq!(object.list_keys("").await.unwrap(), Vec::<String>::new());
+    }
+
+    #[tokio::test]
+    async fn unit_test_18_chunk_parts() {
+        use bytes::Bytes;
+        use futures::StreamExt;
+
+        let chunks = vec![
+            Ok::<_, std::io::Error>(Bytes::from_static(b"abc")),
+            Ok(Bytes::from_static(b"defghij")),
+            Ok(Bytes::from_static(b"k")),
+        ];
+        let parts: Vec<Vec<u8>> = operator::chunk_parts(futures::stream::iter(chunks), 4)
+            .map(|part| part.unwrap())
+            .collect()
+            .await;
+        assert_eq!(
+            parts,
+            vec![b"abcd".to_vec(), b"efgh".to_vec(), b"ijk".to_vec()]
+        );
+
+        let empty = futures::stream::iter(Vec::<Result<Bytes, std::io::Error>>::new());
+        assert_eq!(operator::chunk_parts(empty, 4).count().await, 0);
+
+        let failing = vec![
+            Ok(Bytes::from_static(b"ab")),
+            Err(std::io::Error::other("connection reset")),
+            Ok(Bytes::from_static(b"cd")),
+        ];
+        let parts: Vec<_> = operator::chunk_parts(futures::stream::iter(failing), 4)
+            .collect()
+            .await;
+        assert_eq!(parts.len(), 1);
+        assert!(
+            matches!(&parts[0], Err(error::OperationError::Other(message)) if message == "connection reset")
+        );
+    }
+
+    #[tokio::test]
+    async fn local_test_23_upload_from_stream() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        // 20 MiB in chunks of 1 MiB: two full parts of 8 MiB and the last part of 4 MiB
+        let data: Vec<u8> = (0..20 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        let chunks: Vec<Result<bytes::Bytes, std::io::Error>> = data
+            .chunks(1024 * 1024)
+            .map(|chunk| Ok(bytes::Bytes::copy_from_slice(chunk)))
+            .collect();
+        object
+            .upload_from_stream(
+                "upload_from_stream.bin",
+                "application/octet-stream",
+                futures::stream::iter(chunks),
+            )
+            .await?;
+        assert_eq!(object.download("upload_from_stream.bin").await?, data);
+
+        object.delete("upload_from_stream.bin").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn local_test_24_download_stream() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        use futures::StreamExt;
+
+        object
+            .upload_binary("download_stream.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let stream = object.download_stream("download_stream.txt").await?;
+        // the stream does not borrow the operator, so it can be moved to another task
+        let chunks: Vec<_> = tokio::spawn(stream.collect::<Vec<_>>()).await.unwrap();
+        let mut bin = Vec::new();
+        for chunk in chunks {
+            bin.extend_from_slice(&chunk?);
+        }
+        assert_eq!(bin, b"Hello, World!");
+
+        object.delete("download_stream.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unit_test_19_minio_setup() {
+        let (endpoint, requests) = serve_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><KeyCount>1</KeyCount><IsTruncated>false</IsTruncated><Contents><Key>test.txt</Key><Size>5</Size></Contents></ListBucketResult>"#,
+        )
+        .await;
+        let minio = |endpoint: &str| {
+            Builder::new()
+                .set_bucket_name("bucket".to_string())
+                .set_access_key_id("minioadmin".to_string())
+                .set_secret_access_key("minioadmin".to_string())
+                .set_endpoint(endpoint.to_string())
+                .set_region("us-east-1".to_string())
+                .set_force_path_style(true)
+        };
+        // an http endpoint which is not localhost is rejected unless insecure endpoints are allowed
+        assert!(matches!(
+            minio("http://minio:9000").create_client_result(),
+            Err(error::BuilderError::InsecureEndpointError(_))
+        ));
+        assert!(minio("http://minio:9000")
+            .set_allow_insecure(true)
+            .create_client_result()
+            .is_ok());
+        let object = minio(&endpoint)
+            .set_allow_insecure(true)
+            .create_client_result()
+            .unwrap();
+
+        assert_eq!(object.list_objects().await.unwrap(), vec!["test.txt"]);
+
+        let requests = requests.lock().unwrap();
+        let request = requests[0].to_ascii_lowercase();
+        // path style addressing
+        assert!(
+            request.starts_with("get /bucket/?list-type=2"),
+            "{}",
+            request
+        );
+        assert!(request.contains("host: 127.0.0.1:"), "{}", request);
+        // signed for the fixed region
+        assert!(
+            request.contains("/us-east-1/s3/aws4_request"),
+            "{}",
+            request
+        );
+    }
+
+    #[tokio::test]
+    async fn local_test_25_copy_object_if_match() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("copy_if_match.txt", "text/plain", b"Hello", None)
+            .await?;
+        let e_tag = object
+            .head_object("copy_if_match.txt")
+            .await?
+            .e_tag
+            .expect("ETag not found.");
+
+        let copied = object
+            .copy_object_if_match("copy_if_match.txt", "copy_if_match_copied.txt", &e_tag)
+            .await?;
+        assert_eq!(copied.size, 5);
+
+        // the source was changed after the ETag was taken
+        object
+            .upload_binary("copy_if_match.txt", "text/plain", b"World", None)
+            .await?;
+        assert!(matches!(
+            object
+                .copy_object_if_match("copy_if_match.txt", "copy_if_match_copied.txt", &e_tag)
+                .await,
+            Err(error::OperationError::PreconditionFailed(_))
+        ));
+        assert_eq!(object.download("copy_if_match_copied.txt").await?, b"Hello");
+
+        object.delete("copy_if_match.txt").await?;
+        object.delete("copy_if_match_copied.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn local_test_26_download_ranges() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("download_ranges.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let ranges = object
+            .download_ranges("download_ranges.txt", &[(7, 11), (0, 4), (12, 100)])
+            .await?;
+        assert_eq!(
+            ranges,
+            vec![b"World".to_vec(), b"Hello".to_vec(), b"!".to_vec()]
+        );
+        assert!(object
+            .download_ranges("download_ranges.txt", &[])
+            .await?
+            .is_empty());
+
+        object.delete("download_ranges.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unit_test_20_credentials_provider() {
+        let (endpoint, requests) = serve_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><KeyCount>0</KeyCount><IsTruncated>false</IsTruncated></ListBucketResult>"#,
+        )
+        .await;
+        let credentials = aws_sdk_s3::config::Credentials::new(
+            "provided_access_key_id",
+            "provided_secret_access_key",
+            None,
+            None,
+            "test",
+        );
+        // the access key id and the secret access key are not needed with a provider
+        let object = Builder::new()
+            .set_bucket_name("bucket".to_string())
+            .set_credentials_provider(credentials)
+            .set_endpoint(endpoint)
+            .set_force_path_style(true)
+            .create_client_result()
+            .unwrap();
+        object.list_objects().await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert!(
+            requests[0].contains("Credential=provided_access_key_id/"),
+            "{}",
+            requests[0]
+        );
+    }
+
+    #[tokio::test]
+    async fn local_test_27_bucket_cors() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let original = object.get_bucket_cors().await?;
+
+        let rules = vec![object::CorsRule {
+            allowed_origins: vec!["https://example.com".to_string()],
+            allowed_methods: vec!["GET".to_string()],
+            allowed_headers: vec!["*".to_string()],
+            expose_headers: vec!["ETag".to_string()],
+            max_age_seconds: Some(3600),
+        }];
+        object.put_bucket_cors(&rules).await?;
+        assert_eq!(object.get_bucket_cors().await?, rules);
+
+        object.delete_bucket_cors().await?;
+        assert!(object.get_bucket_cors().await?.is_empty());
+
+        // restore the original rules
+        if !original.is_empty() {
+            object.put_bucket_cors(&original).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn local_test_28_bucket_lifecycle() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let original = object.get_bucket_lifecycle().await?;
+
+        let rules = vec![object::LifecycleRule::new("test-expire-tmp")
+            .set_prefix("tmp/")
+            .set_expiration_days(7)
+            .set_abort_incomplete_multipart_upload_days(1)];
+        object.put_bucket_lifecycle(&rules).await?;
+        let stored = object.get_bucket_lifecycle().await?;
+        assert!(stored.contains(&rules[0]));
+
+        // restore the original rules
+        if original.is_empty() {
+            object.delete_bucket_lifecycle().await?;
+        } else {
+            object.put_bucket_lifecycle(&original).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unit_test_21_upload_binary_content_length() {
+        let (endpoint, requests) = serve_xml("").await;
+        let object = local_operator(&endpoint);
+
+        object
+            .upload_binary("test.txt", "text/plain", b"Hello, World!", None)
+            .await
+            .unwrap();
+
+        let requests = requests.lock().unwrap();
+        let request = requests[0].to_ascii_lowercase();
+        assert!(request.starts_with("put /bucket/test.txt"), "{}", request);
+        assert!(request.contains("content-length: 13\r\n"), "{}", request);
+        assert!(
+            !request.contains("transfer-encoding: chunked"),
+            "{}",
+            request
+        );
+    }
+
+    #[cfg(feature = "decompress")]
+    #[test]
+    fn unit_test_22_decompress() {
+        use decompress::decode;
+
+        // gzip (fixed Huffman block)
+        let gzip = b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\xf3\x48\xcd\xc9\xc9\xd7\x51\x08\xcf\x2f\xca\x49\x51\x04\x00\xd0\xc3\x4a\xec\x0d\x00\x00\x00";
+        assert_eq!(decode("gzip", gzip.to_vec()).unwrap(), b"Hello, World!");
+
+        // zlib (dynamic Huffman block)
+        let text: Vec<u8> = (0..200)
+            .flat_map(|i| {
+                format!(
+                    "line {}: the quick brown fox jumps over the lazy dog\n",
+                    i % 17
+                )
+                .into_bytes()
+            })
+            .collect();
+        let zlib = b"\x78\xda\xed\xd3\xcb\x11\x82\x40\x10\x45\xd1\xbd\x51\x74\x08\xb6\xf8\xc3\x6c\x50\x07\x45\
+            \x47\x06\x51\x54\x88\x9e\x82\x08\xac\xb7\xbe\xeb\xae\xbb\xea\x77\x62\x55\x07\x5b\x1e\xec\
+            \x7d\x0d\xf6\xec\xaa\xd3\xdd\x8e\x6d\xfa\xd6\x56\xa6\x9f\xdd\xba\x47\xf3\xb2\xf4\x09\xed\
+            \x7c\x8e\xc5\xd0\xdb\x39\x5d\x16\x71\x6a\x5c\x68\x56\x42\x93\x09\xcd\x5a\x68\x36\x42\xb3\
+            \x15\x9a\x9d\xd0\xec\x85\x26\x57\x7e\x2a\x0d\x41\x59\x82\x2b\x53\x70\x65\x0b\xae\x8c\xc1\
+            \x95\x35\xb8\x32\x07\xe8\x41\x0f\x7a\xd0\x83\x1e\xf4\xa0\x07\x3d\xe8\x41\x0f\x7a\xd0\x83\
+            \x1e\xf4\xa0\x07\x3d\xe8\x41\x0f\x7a\xd0\x83\x1e\xf4\xa0\x07\x3d\xe8\x41\x0f\x7a\xd0\x83\
+            \x1e\xf4\xa0\x07\x3d\xe8\x41\x0f\x7a\xd0\x83\xde\x7f\xd1\x08\xad\xcb\x65\x91";
+        assert_eq!(decode("deflate", zlib.to_vec()).unwrap(), text);
+
+        // zlib (stored block) and raw deflate
+        let stored = b"\x78\x01\x01\x06\x00\xf9\xff\x73\x74\x6f\x72\x65\x64\x09\x3c\x02\x92";
+        assert_eq!(decode("deflate", stored.to_vec()).unwrap(), b"stored");
+        let raw = b"\x4b\x4c\x4a\x4e\x84\x21\x00";
+        assert_eq!(decode("deflate", raw.to_vec()).unwrap(), b"abcabcabcabc");
+
+        // identity and unsupported encodings
+        assert_eq!(decode("", b"plain".to_vec()).unwrap(), b"plain");
+        assert!(matches!(
+            decode("compress", b"plain".to_vec()),
+            Err(error::OperationError::UnsupportedEncoding(encoding)) if encoding == "compress"
+        ));
+        assert!(matches!(
+            decode("gzip", b"plain text".to_vec()),
+            Err(error::OperationError::DecompressError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn unit_test_23_content_type() {
+        use error::OperationError;
+        use operator::check_content_type;
+
+        assert!(check_content_type("text/plain").is_ok());
+        assert!(check_content_type("text/plain; charset=utf-8").is_ok());
+        assert!(check_content_type(operator::OCTET_STREAM).is_ok());
+        assert!(check_content_type("application/vnd.api+json").is_ok());
+        for invalid in [
+            "",
+            " ",
+            "text",
+            "text/",
+            "/plain",
+            "text plain/x",
+            "text/plain\r\nX: y",
+        ] {
+            assert!(
+                matches!(
+                    check_content_type(invalid),
+                    Err(OperationError::InvalidContentType(_))
+                ),
+                "{:?}",
+                invalid
+            );
+        }
+
+        // rejected before any request is sent
+        let object = local_operator("http://127.0.0.1:9");
+        assert!(matches!(
+            object.upload_binary("test.txt", "", b"Hello", None).await,
+            Err(OperationError::InvalidContentType(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn unit_test_24_retry_classifier() {
+        let (endpoint, requests) = serve_response(
+            "499 Proxy Busy",
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>ProxyBusy</Code><Message>busy</Message></Error>"#,
+        )
+        .await;
+        let builder = Builder::new()
+            .set_bucket_name("bucket".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint(endpoint)
+            .set_force_path_style(true)
+            .set_max_attempts(2);
+
+        // 499 is not retried by default
+        let object = builder.clone().create_client_result().unwrap();
+        assert!(object.download("test.txt").await.is_err());
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        let object = builder
+            .set_retry_classifier(|err: &error::R2Error| {
+                err.status == Some(499)
+                    && err.code == Some(error::S3ErrorCode::Unknown("ProxyBusy".to_string()))
+            })
+            .create_client_result()
+            .unwrap();
+        assert!(object.download("test.txt").await.is_err());
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn local_test_29_sniff() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("sniff.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+        object
+            .upload_binary("sniff_empty.txt", "text/plain", b"", None)
+            .await?;
+
+        assert_eq!(object.sniff("sniff.txt", 5).await?, b"Hello");
+        assert_eq!(object.sniff("sniff.txt", 100).await?, b"Hello, World!");
+        assert!(object.sniff("sniff.txt", 0).await?.is_empty());
+        assert!(object.sniff("sniff_empty.txt", 5).await?.is_empty());
+
+        object.delete("sniff.txt").await?;
+        object.delete("sniff_empty.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unit_test_25_list_objects_detailed() {
+        let (endpoint, requests) = serve_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><Prefix></Prefix><MaxKeys>1000</MaxKeys><KeyCount>2</KeyCount><IsTruncated>false</IsTruncated><Contents><Key>a.txt</Key><Size>1</Size><ETag>"a"</ETag><Owner><ID>owner-id</ID><DisplayName>owner-name</DisplayName></Owner><RestoreStatus><IsRestoreInProgress>true</IsRestoreInProgress></RestoreStatus></Contents><Contents><Key>b.txt</Key><Size>2</Size><Owner><ID>owner-id</ID></Owner></Contents></ListBucketResult>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+
+        let files = object.list_objects_detailed("").await.unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].owner.as_deref(), Some("owner-name"));
+        assert_eq!(
+            files[0].restore_status,
+            Some(object::RestoreStatus {
+                in_progress: true,
+                expiry_date: None,
+            })
+        );
+        assert_eq!(files[1].owner.as_deref(), Some("owner-id"));
+        assert_eq!(files[1].restore_status, None);
+
+        let request = requests.lock().unwrap()[0].to_lowercase();
+        assert!(request.contains("fetch-owner=true"));
+        assert!(request.contains("x-amz-optional-object-attributes: restorestatus"));
+    }
+
+    #[tokio::test]
+    async fn local_test_30_list_objects_detailed() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("list_objects_detailed/a.txt", "text/plain", b"a", None)
+            .await?;
+
+        let files = object
+            .list_objects_detailed("list_objects_detailed/")
+            .await?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].key, "list_objects_detailed/a.txt");
+        assert_eq!(files[0].size, 1);
+
+        object.delete("list_objects_detailed/a.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unit_test_26_delete_version() {
+        let (endpoint, requests) = serve_xml("").await;
+        let object = local_operator(&endpoint);
+
+        object.delete_version("a.txt", "version-1").await.unwrap();
+
+        let request = requests.lock().unwrap()[0].to_lowercase();
+        assert!(request.starts_with("delete /bucket/a.txt?"));
+        assert!(request.contains("versionid=version-1"));
+    }
+
+    #[tokio::test]
+    async fn unit_test_27_file_matches_etag() {
+        let dir = env::temp_dir().join("cf_r2_sdk_file_matches_etag");
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+
+        // single part ETag
+        let path = dir.join("small.txt");
+        tokio::fs::write(&path, b"Hello, World!").await.unwrap();
+        let e_tag = operator::md5_etag(&operator::md5_digest(b"Hello, World!"));
+        assert!(sync::file_matches_etag(&path, 13, &e_tag).await.unwrap());
+        let e_tag = operator::md5_etag(&operator::md5_digest(b"Hello, world!"));
+        assert!(!sync::file_matches_etag(&path, 13, &e_tag).await.unwrap());
+
+        // multipart ETag of 8 MiB parts
+        let path = dir.join("large.bin");
+        let data: Vec<u8> = (0..9 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        tokio::fs::write(&path, &data).await.unwrap();
+        let (first, second) = data.split_at(8 * 1024 * 1024);
+        let e_tag =
+            operator::multipart_etag(&[operator::md5_digest(first), operator::md5_digest(second)]);
+        let size = data.len() as u64;
+        assert!(sync::file_matches_etag(&path, size, &e_tag).await.unwrap());
+        assert!(!sync::file_matches_etag(&path, size, "\"0123-2\"")
+            .await
+            .unwrap());
+        assert!(!sync::file_matches_etag(&path, size, "\"0123-x\"")
+            .await
+            .unwrap());
+
+        tokio::fs::remove_dir_all(&dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn local_test_31_sync_up_with_options() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let local_dir = env::temp_dir().join("cf_r2_sdk_sync_up_with_options");
+        std::fs::create_dir_all(&local_dir).unwrap();
+        std::fs::write(local_dir.join("a.txt"), b"Hello, World!").unwrap();
+
+        let options = options::SyncUpOptions::new().set_compare_content(true);
+        let report = object
+            .sync_up_with_options(&local_dir, "sync_up_with_options", &options)
+            .await?;
+        assert_eq!(report.uploaded, 1);
+
+        // the same content is skipped
+        let report = object
+            .sync_up_with_options(&local_dir, "sync_up_with_options", &options)
+            .await?;
+        assert_eq!(report.skipped, 1);
+
+        // a change keeping the size is uploaded
+        std::fs::write(local_dir.join("a.txt"), b"Hello, world!").unwrap();
+        let report = object
+            .sync_up_with_options(&local_dir, "sync_up_with_options", &options)
+            .await?;
+        assert_eq!(report.uploaded, 1);
+        assert_eq!(
+            object.download("sync_up_with_options/a.txt").await?,
+            b"Hello, world!"
+        );
+
+        object.delete("sync_up_with_options/a.txt").await?;
+        std::fs::remove_dir_all(&local_dir).unwrap();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unit_test_28_download_to_file() {
+        let dir = env::temp_dir().join("cf_r2_sdk_download_to_file");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("a.txt");
+
+        let (endpoint, _) = serve_xml("Hello, World!").await;
+        let object = local_operator(&endpoint);
+        object.download_to_file("a.txt", &path).await.unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"Hello, World!");
+
+        // a failed download keeps the existing file and leaves no temporary file
+        let (endpoint, _) = serve_response(
+            "404 Not Found",
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>NoSuchKey</Code><Message>not found</Message></Error>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+        assert!(object.download_to_file("a.txt", &path).await.is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"Hello, World!");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn local_test_32_download_to_file() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("download_to_file.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let path = env::temp_dir().join("cf_r2_sdk_download_to_file.txt");
+        object
+            .download_to_file("download_to_file.txt", &path)
+            .await?;
+        assert_eq!(std::fs::read(&path).unwrap(), b"Hello, World!");
+
+        object.delete("download_to_file.txt").await?;
+        std::fs::remove_file(&path).unwrap();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unit_test_29_public_url() {
+        let builder = Builder::new()
+            .set_bucket_name("bucket".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint("https://account_id.r2.cloudflarestorage.com/".to_string());
+        let object = builder.clone().create_client_result().unwrap();
+        assert_eq!(
+            object.public_url("a/b c.txt").as_deref(),
+            Some("https://account_id.r2.cloudflarestorage.com/bucket/a/b%20c.txt")
+        );
+        let object = builder
+            .set_public_base_url("https://assets.example.com/".to_string())
+            .create_client_result()
+            .unwrap();
+        assert_eq!(
+            object.public_url("a/b c.txt").as_deref(),
+            Some("https://assets.example.com/a/b%20c.txt")
+        );
+
+        // no URL is available, so nothing is uploaded
+        let (endpoint, requests) = serve_xml("").await;
+        let object = local_operator(&endpoint);
+        assert_eq!(object.public_url("a.txt"), None);
+        assert!(matches!(
+            object
+                .upload_and_url("a.txt", "text/plain", b"a", None)
+                .await,
+            Err(error::OperationError::PublicUrlNotSet)
+        ));
+        assert!(requests.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn local_test_33_upload_and_url() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let url = object
+            .upload_and_url("upload_and_url.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+        assert!(url.ends_with("/upload_and_url.txt"));
+        assert_eq!(
+            object.download("upload_and_url.txt").await?,
+            b"Hello, World!"
+        );
+
+        object.delete("upload_and_url.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unit_test_30_checksum_algorithm() {
+        let path = env::temp_dir().join("cf_r2_sdk_checksum_algorithm.txt");
+        std::fs::write(&path, b"Hello, World!").unwrap();
+        let (endpoint, requests) = serve_xml("").await;
+        let object = local_operator(&endpoint);
+
+        let options =
+            options::UploadOptions::new().set_checksum_algorithm(options::ChecksumAlgorithm::Crc32);
+        object
+            .upload_file_streaming("a.txt", "text/plain", &path, &options)
+            .await
+            .unwrap();
+
+        let request = requests.lock().unwrap()[0].to_lowercase();
+        assert!(request.contains("x-amz-sdk-checksum-algorithm: crc32"));
+        assert!(request.contains("x-amz-trailer: x-amz-checksum-crc32"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn local_test_34_checksum_algorithm() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let options = options::UploadOptions::new()
+            .set_checksum_algorithm(options::ChecksumAlgorithm::Sha256);
+        object
+            .upload_file_streaming(
+                "checksum_algorithm.jpg",
+                "image/jpeg",
+                "./data/sample.jpg",
+                &options,
+            )
+            .await?;
+        assert_eq!(
+            object.download("checksum_algorithm.jpg").await?,
+            std::fs::read("./data/sample.jpg").unwrap()
+        );
+
+        object.delete("checksum_algorithm.jpg").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn local_test_35_verify() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("verify.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+        assert!(object.verify("verify.txt").await?);
+
+        // a multipart upload has a composite ETag
+        let data: Vec<u8> = (0..9 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        let chunks = data
+            .chunks(1024 * 1024)
+            .map(|chunk| Ok::<_, std::io::Error>(bytes::Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>();
+        object
+            .upload_from_stream(
+                "verify.bin",
+                "application/octet-stream",
+                futures::stream::iter(chunks),
+            )
+            .await?;
+        assert!(object.verify("verify.bin").await?);
+
+        object.delete("verify.txt").await?;
+        object.delete("verify.bin").await?;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unit_test_31_non_utf8_path() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = env::temp_dir().join(std::ffi::OsStr::from_bytes(b"cf_r2_sdk_\xff.txt"));
+        std::fs::write(&path, b"Hello, World!").unwrap();
+        let (endpoint, requests) = serve_xml("").await;
+        let object = local_operator(&endpoint);
+
+        object
+            .upload_file("a.txt", "text/plain", &path, None)
+            .await
+            .unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn unit_test_32_prefix_stats() {
+        let (endpoint, _) = serve_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><Prefix>a/</Prefix><MaxKeys>1000</MaxKeys><KeyCount>2</KeyCount><IsTruncated>false</IsTruncated><Contents><Key>a/1.txt</Key><Size>3</Size></Contents><Contents><Key>a/2.txt</Key><Size>4</Size></Contents></ListBucketResult>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+
+        assert_eq!(
+            object.prefix_stats("a/").await.unwrap(),
+            object::PrefixStats {
+                object_count: 2,
+                total_size: 7,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn local_test_36_prefix_stats() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("prefix_stats/a.txt", "text/plain", b"a", None)
+            .await?;
+        object
+            .upload_binary("prefix_stats/b.txt", "text/plain", b"bb", None)
+            .await?;
+
+        let stats = object.prefix_stats("prefix_stats/").await?;
+        assert_eq!(stats.object_count, 2);
+        assert_eq!(stats.total_size, 3);
+
+        object.delete("prefix_stats/a.txt").await?;
+        object.delete("prefix_stats/b.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unit_test_33_auth_error() {
+        let (endpoint, _) = serve_response(
+            "403 Forbidden",
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>InvalidAccessKeyId</Code><Message>The AWS Access Key Id you provided does not exist in our records.</Message></Error>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+
+        let err = object.download("a.txt").await.unwrap_err();
+        assert!(matches!(err, error::OperationError::AuthError(_)));
+        assert_eq!(err.code(), Some(&error::S3ErrorCode::InvalidAccessKeyId));
+        assert!(matches!(
+            object.list_objects().await,
+            Err(error::OperationError::AuthError(_))
+        ));
+
+        // other errors keep their variant
+        let (endpoint, _) = serve_response(
+            "403 Forbidden",
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>AccessDenied</Code><Message>Access Denied</Message></Error>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+        assert!(matches!(
+            object.download("a.txt").await,
+            Err(error::OperationError::AWSSdkS3GetObjectError(_))
+        ));
+    }
+
+    #[cfg(feature = "head-cache")]
+    #[tokio::test]
+    async fn unit_test_34_head_object_cached() {
+        use std::time::Duration;
+
+        let (endpoint, requests) = serve_xml("").await;
+        let builder = Builder::new()
+            .set_bucket_name("bucket".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint(endpoint)
+            .set_force_path_style(true);
+
+        // fresh metadata is returned without a request
+        let object = builder
+            .clone()
+            .set_head_cache(1, Duration::from_secs(3600))
+            .create_client_result()
+            .unwrap();
+        object.head_object_cached("a.txt").await.unwrap();
+        object.head_object_cached("a.txt").await.unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        // the least recently used file is evicted
+        object.head_object_cached("b.txt").await.unwrap();
+        object.head_object_cached("a.txt").await.unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 3);
+
+        // invalidated metadata is requested again
+        object.invalidate_head_cache("a.txt");
+        object.head_object_cached("a.txt").await.unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 4);
+
+        // stale metadata is revalidated
+        let object = builder
+            .set_head_cache(1, Duration::ZERO)
+            .create_client_result()
+            .unwrap();
+        object.head_object_cached("a.txt").await.unwrap();
+        object.head_object_cached("a.txt").await.unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 6);
+    }
+
+    #[test]
+    fn unit_test_35_key_for_path() {
+        use std::path::Path;
+
+        assert_eq!(
+            sync::key_for_path(Path::new("./photos/2024/a.jpg"), "backup").as_deref(),
+            Some("backup/photos/2024/a.jpg")
+        );
+        assert_eq!(
+            sync::key_for_path(Path::new("a.jpg"), "").as_deref(),
+            Some("a.jpg")
+        );
+        assert_eq!(
+            sync::key_for_path(Path::new("/home/user/a.jpg"), "backup/").as_deref(),
+            Some("backup/a.jpg")
+        );
+        assert_eq!(sync::key_for_path(Path::new("../a.jpg"), "backup"), None);
+        assert_eq!(sync::key_for_path(Path::new("."), "backup"), None);
+        assert_eq!(sync::key_for_path(Path::new("/"), "backup"), None);
+    }
+
+    #[tokio::test]
+    async fn local_test_37_upload_file_as() -> Result<(), Error> {
+        // load .env file
+        dotenv().expect(".env file not found.");
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let key = object
+            .upload_file_as("./data/sample.jpg", "upload_file_as", None)
+            .await?;
+        assert_eq!(key, "upload_file_as/data/sample.jpg");
+        assert_eq!(
+            object.download(&key).await?,
+            std::fs::read("./data/sample.jpg").unwrap()
+        );
+
+        object.delete(&key).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn unit_test_36_request_payer() {
+        let (endpoint, requests) = serve_xml("").await;
+        let object = local_operator(&endpoint);
+        object
+            .upload_binary("a.txt", "text/plain", b"Hello, World!", None)
+            .await
+            .unwrap();
+        let object = object.with_request_payer(true);
+        object
+            .upload_binary("a.txt", "text/plain", b"Hello, World!", None)
+            .await
+            .unwrap();
+        object.download("a.txt").await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert!(!requests[0]
+            .to_lowercase()
+            .contains("x-amz-request-payer: requester"));
+        assert!(requests[1]
+            .to_lowercase()
+            .contains("x-amz-request-payer: requester"));
+        assert!(requests[2]
+            .to_lowercase()
+            .contains("x-amz-request-payer: requester"));
+    }
+
+    #[tokio::test]
+    async fn unit_test_37_with_timeout() {
+        use std::time::Duration;
+
+        // accept the connections without ever responding
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut streams = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                streams.push(stream);
+            }
+        });
+        let object = local_operator(&endpoint).with_timeout(Duration::from_millis(200));
+
+        let start = std::time::Instant::now();
+        let result = object.download("a.txt").await;
+        assert!(matches!(
+            result,
+            Err(error::OperationError::Timeout(timeout)) if timeout == Duration::from_millis(200)
+        ));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn unit_test_38_redirect() {
+        let (endpoint, _) = serve_with_headers(
+            "307 Temporary Redirect",
+            "Location: https://other.example.com/bucket/a.txt\r\n",
+            "",
+        )
+        .await;
+        let object = local_operator(&endpoint);
+
+        match object.download("a.txt").await {
+            Err(error::OperationError::Redirect { location }) => {
+                assert_eq!(location, "https://other.example.com/bucket/a.txt")
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn unit_test_39_custom_headers() {
+        let (endpoint, requests) = serve_xml("").await;
+        let builder = Builder::new()
+            .set_bucket_name("bucket".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint(endpoint)
+            .set_force_path_style(true);
+
+        let object = builder
+            .clone()
+            .set_header("x-correlation-id", "static-id")
+            .set_header_fn("x-trace-id", || Some("trace-1".to_string()))
+            .set_header_fn("x-span-id", || None)
+            .create_client_result()
+            .unwrap();
+        object.download("a.txt").await.unwrap();
+        let request = requests.lock().unwrap()[0].to_lowercase();
+        assert!(request.contains("x-correlation-id: static-id"));
+        assert!(request.contains("x-trace-id: trace-1"));
+        assert!(!request.contains("x-span-id"));
+
+        assert!(matches!(
+            builder
+                .set_header("x correlation id", "static-id")
+                .create_client_result(),
+            Err(error::BuilderError::InvalidHeaderError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn unit_test_40_copy_objects() {
+        let (endpoint, requests) = serve_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?><CopyObjectResult><ETag>"etag"</ETag></CopyObjectResult>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+
+        let results = object
+            .copy_objects(&[("a.txt", "a_copy.txt"), ("b.txt", "b_copy.txt")])
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| result.is_ok()));
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        for (source, destination) in [("a.txt", "a_copy.txt"), ("b.txt", "b_copy.txt")] {
+            assert!(requests.iter().any(|request| {
+                let request = request.to_lowercase();
+                request.starts_with(&format!("put /bucket/{}?", destination))
+                    && request.contains(&format!("x-amz-copy-source: bucket/{}", source))
+            }));
+        }
+    }
+
+    #[tokio::test]
+    async fn unit_test_41_unquoted_etag() {
+        let (endpoint, _) = serve_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><Prefix></Prefix><MaxKeys>1000</MaxKeys><KeyCount>1</KeyCount><IsTruncated>false</IsTruncated><Contents><Key>a.txt</Key><Size>13</Size><ETag>&quot;65a8e27d8879283831b664bd8b7f0ad4&quot;</ETag></Contents></ListBucketResult>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+
+        let files = object.list_objects_detailed("").await.unwrap();
+        assert_eq!(
+            files[0].e_tag.as_deref(),
+            Some("65a8e27d8879283831b664bd8b7f0ad4")
+        );
+
+        assert_eq!(operator::unquote_etag("\"abc-2\""), "abc-2");
+        assert_eq!(operator::unquote_etag("abc"), "abc");
+        assert_eq!(operator::quote_etag("abc"), "\"abc\"");
+        assert_eq!(operator::quote_etag("\"abc\""), "\"abc\"");
+    }
+
+    #[tokio::test]
+    async fn unit_test_42_download_part() {
+        let (endpoint, requests) = serve_xml("Hello, World!").await;
+        let object = local_operator(&endpoint);
+
+        assert_eq!(
+            object.download_part("a.txt", 1).await.unwrap(),
+            b"Hello, World!"
+        );
+        assert!(requests.lock().unwrap()[0].contains("partNumber=1"));
+
+        // the mock server ignores partNumber and returns the whole file
+        assert!(matches!(
+            object.download_part("a.txt", 2).await,
+            Err(error::OperationError::NotSupported(_))
+        ));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn unit_test_43_blocking_shared_runtime() {
+        // serve the mock from its own runtime, like a server outside of the program
+        let server = tokio::runtime::Runtime::new().unwrap();
+        let (endpoint, requests) = server.block_on(serve_xml("Hello, World!"));
+        let object = blocking::Operator::new(local_operator(&endpoint), None);
+
+        assert_eq!(object.download("a.txt").unwrap(), b"Hello, World!");
+        // the second call reuses the runtime created by the first one
+        let handle = std::thread::spawn(move || object.download("b.txt").unwrap());
+        assert_eq!(handle.join().unwrap(), b"Hello, World!");
+        assert_eq!(requests.lock().unwrap().len(), 2);
+
+        let object =
+            blocking::Operator::new(local_operator(&endpoint), Some(server.handle().clone()));
+        assert_eq!(object.download("c.txt").unwrap(), b"Hello, World!");
+    }
+
+    #[tokio::test]
+    async fn unit_test_44_for_each_object() {
+        let (endpoint, requests) = serve_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><Prefix>photos/</Prefix><MaxKeys>1000</MaxKeys><KeyCount>2</KeyCount><IsTruncated>false</IsTruncated><Contents><Key>photos/a.jpg</Key><Size>1</Size></Contents><Contents><Key>photos/b.jpg</Key><Size>2</Size></Contents></ListBucketResult>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+
+        let mut file_names = Vec::new();
+        object
+            .for_each_object(Some("photos/"), |file_name| {
+                file_names.push(file_name.to_string())
+            })
+            .await
+            .unwrap();
+        assert_eq!(file_names, ["photos/a.jpg", "photos/b.jpg"]);
+        assert!(requests.lock().unwrap()[0].contains("prefix=photos%2F"));
+    }
+
+    #[tokio::test]
+    async fn unit_test_45_upload_with_info() {
+        let (endpoint, _) = serve_with_headers(
+            "200 OK",
+            "ETag: \"65a8e27d8879283831b664bd8b7f0ad4\"\r\n",
+            "",
+        )
+        .await;
+        let object = local_operator(&endpoint);
+
+        let info = object
+            .upload_binary_with_info("a.txt", "text/plain", b"Hello, World!", None)
+            .await
+            .unwrap();
+        assert_eq!(
+            info,
+            object::ObjectInfo {
+                key: "a.txt".to_string(),
+                size: 13,
+                e_tag: Some("65a8e27d8879283831b664bd8b7f0ad4".to_string()),
+                ..object::ObjectInfo::default()
+            }
+        );
+    }
+
+    #[test]
+    fn unit_test_46_r2_preset() {
+        let object = Builder::r2(
+            "0123456789abcdef",
+            "access_key_id",
+            "secret_access_key",
+            "bucket_name",
+        )
+        .unwrap();
+        assert_eq!(
+            object.public_url("a.txt").as_deref(),
+            Some("https://0123456789abcdef.r2.cloudflarestorage.com/bucket_name/a.txt")
+        );
+
+        assert!(matches!(
+            Builder::r2("", "access_key_id", "secret_access_key", "bucket_name"),
+            Err(error::BuilderError::InvalidAccountIdError(_))
+        ));
+        assert!(matches!(
+            Builder::r2(
+                "evil.example.com/",
+                "access_key_id",
+                "secret_access_key",
+                "bucket_name"
+            ),
+            Err(error::BuilderError::InvalidAccountIdError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn unit_test_47_download_range_bounds() {
+        let (endpoint, requests) = serve_with_headers(
+            "416 Requested Range Not Satisfiable",
+            "Content-Range: bytes */13\r\n",
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>InvalidRange</Code><Message>The requested range is not satisfiable</Message></Error>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+
+        // inverted ranges are rejected without a request
+        assert!(matches!(
+            object.download_range("a.txt", Some((5, 2))).await,
+            Err(error::OperationError::InvalidRange {
+                start: 5,
+                end: 2,
+                size: None
+            })
+        ));
+        assert!(requests.lock().unwrap().is_empty());
+
+        assert!(matches!(
+            object.download_range("a.txt", Some((20, 30))).await,
+            Err(error::OperationError::InvalidRange {
+                start: 20,
+                end: 30,
+                size: Some(13)
+            })
+        ));
+        // sniffing an empty file returns no bytes
+        assert_eq!(object.sniff("a.txt", 4).await.unwrap(), Vec::<u8>::new());
+    }
+
+    #[tokio::test]
+    async fn unit_test_48_shared_connection_pool() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::AsyncWriteExt;
+
+        // keep the connections alive and count them
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let connections = std::sync::Arc::new(AtomicUsize::new(0));
+        let accepted = connections.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                accepted.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buffer = [0; 1024];
+                    loop {
+                        match stream.read(&mut buffer).await {
+                            Ok(0) | Err(_) => break,
+                            Ok(n) => request.extend_from_slice(&buffer[..n]),
+                        }
+                        if request.windows(4).any(|window| window == b"\r\n\r\n") {
+                            request.clear();
+                            let response = "HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nHello";
+                            if stream.write_all(response.as_bytes()).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+        let object = local_operator(&endpoint);
+
+        for operator in [
+            object.clone(),
+            object.clone(),
+            object.with_bucket("other"),
+            object.with_timeout(std::time::Duration::from_secs(5)),
+        ] {
+            assert_eq!(operator.download("a.txt").await.unwrap(), b"Hello");
+        }
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn unit_test_49_object_lock() {
+        let path = env::temp_dir().join("cf_r2_sdk_object_lock.txt");
+        std::fs::write(&path, b"Hello, World!").unwrap();
+        let options = options::UploadOptions::new()
+            .set_object_lock(
+                options::ObjectLockMode::Compliance,
+                std::time::UNIX_EPOCH + std::time::Duration::from_secs(4_102_444_800),
+            )
+            .set_legal_hold(true);
+
+        let (endpoint, requests) = serve_xml("").await;
+        let object = local_operator(&endpoint);
+        object
+            .upload_file_streaming("a.txt", "text/plain", &path, &options)
+            .await
+            .unwrap();
+        let request = requests.lock().unwrap()[0].to_lowercase();
+        assert!(request.contains("x-amz-object-lock-mode: compliance"));
+        assert!(request.contains("x-amz-object-lock-retain-until-date: 2100-01-01t00:00:00z"));
+        assert!(request.contains("x-amz-object-lock-legal-hold: on"));
+        assert!(request.contains("x-amz-sdk-checksum-algorithm: crc32"));
+
+        let (endpoint, _) = serve_response(
+            "400 Bad Request",
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>InvalidRequest</Code><Message>Bucket is missing Object Lock Configuration</Message></Error>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+        assert!(matches!(
+            object
+                .upload_file_streaming("a.txt", "text/plain", &path, &options)
+                .await,
+            Err(error::OperationError::NotSupported(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn unit_test_50_restore_object() {
+        let (endpoint, requests) = serve_xml("").await;
+        let object = local_operator(&endpoint);
+        object.restore_object("a.txt", 7).await.unwrap();
+        assert!(requests.lock().unwrap()[0].starts_with("POST /bucket/a.txt?restore"));
+
+        let (endpoint, _) = serve_response(
+            "501 Not Implemented",
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>NotImplemented</Code><Message>RestoreObject is not implemented</Message></Error>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+        assert!(matches!(
+            object.restore_object("a.txt", 7).await,
+            Err(error::OperationError::NotSupported(_))
+        ));
+
+        assert_eq!(
+            operator::restore_status(
+                r#"ongoing-request="false", expiry-date="Fri, 21 Dec 2012 00:00:00 GMT""#
+            ),
+            object::RestoreStatus {
+                in_progress: false,
+                expiry_date: Some(
+                    std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_356_048_000)
+                ),
+            }
+        );
+        assert_eq!(
+            operator::restore_status(r#"ongoing-request="true""#),
+            object::RestoreStatus {
+                in_progress: true,
+                expiry_date: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn unit_test_51_retry_signature_errors() {
+        let (endpoint, requests) = serve_response(
+            "403 Forbidden",
+            r#"<?xml version="1.0" encoding="UTF-8"?><Error><Code>SignatureDoesNotMatch</Code><Message>signature</Message></Error>"#,
+        )
+        .await;
+        let builder = Builder::new()
+            .set_bucket_name("bucket".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint(endpoint)
+            .set_force_path_style(true)
+            .set_max_attempts(2);
+
+        // not retried by default
+        let object = builder.clone().create_client_result().unwrap();
+        let err = object.download("test.txt").await.unwrap_err();
+        assert!(matches!(err, error::OperationError::AuthError(_)));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        // retried once, then still an authentication error
+        let object = builder
+            .set_retry_signature_errors(true)
+            .create_client_result()
+            .unwrap();
+        let err = object.download("test.txt").await.unwrap_err();
+        assert!(matches!(err, error::OperationError::AuthError(_)));
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn unit_test_52_list_objects_url_encoding() {
+        let (endpoint, requests) = serve_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><EncodingType>url</EncodingType><KeyCount>3</KeyCount><IsTruncated>false</IsTruncated><Contents><Key>line%0Abreak.txt</Key><Size>5</Size></Contents><Contents><Key>a+b%2Bc.txt</Key><Size>5</Size></Contents><Contents><Key>%E6%97%A5%E6%9C%AC.txt</Key><Size>5</Size></Contents></ListBucketResult>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+
+        assert_eq!(
+            object.list_objects().await.unwrap(),
+            vec!["line\nbreak.txt", "a b+c.txt", "日本.txt"]
+        );
+        assert!(requests.lock().unwrap()[0].contains("encoding-type=url"));
+
+        // a key which is not percent-encoded is kept as it is
+        assert_eq!(operator::decode_key("100%.txt"), "100%.txt");
+    }
+
+    #[tokio::test]
+    async fn unit_test_53_move_object_conditional() {
+        const HEAD: (&str, &str, &str) = ("200 OK", "ETag: \"source\"\r\n", "");
+        const COPY: (&str, &str, &str) = (
+            "200 OK",
+            "",
+            r#"<?xml version="1.0" encoding="UTF-8"?><CopyObjectResult><ETag>"copy"</ETag></CopyObjectResult>"#,
+        );
+        const DELETED: (&str, &str, &str) = ("204 No Content", "", "");
+
+        // the source and the copy are deleted only if they were not changed
+        let (endpoint, requests) = serve_sequence(vec![HEAD, COPY, DELETED]).await;
+        let object = local_operator(&endpoint);
+        object.move_object("move.txt", "moved.txt").await.unwrap();
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[1]
+            .to_lowercase()
+            .contains("x-amz-copy-source-if-match: \"source\""));
+        assert!(requests[2].starts_with("DELETE /bucket/move.txt"));
+        assert!(requests[2].to_lowercase().contains("if-match: \"source\""));
+
+        // the source was modified after the copy, so the copy is removed
+        let (endpoint, requests) = serve_sequence(vec![
+            HEAD,
+            COPY,
+            ("412 Precondition Failed", "", ""),
+            DELETED,
+        ])
+        .await;
+        let object = local_operator(&endpoint);
+        assert!(matches!(
+            object.move_object("move.txt", "moved.txt").await,
+            Err(error::OperationError::PreconditionFailed(_))
+        ));
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 4);
+        assert!(requests[3].starts_with("DELETE /bucket/moved.txt"));
+        assert!(requests[3].to_lowercase().contains("if-match: \"copy\""));
+
+        // the source was moved by another process, so the copy is kept
+        let (endpoint, requests) =
+            serve_sequence(vec![HEAD, COPY, ("404 Not Found", "", "")]).await;
+        let object = local_operator(&endpoint);
+        assert!(matches!(
+            object.move_object("move.txt", "moved.txt").await,
+            Err(error::OperationError::PreconditionFailed(_))
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 3);
+
+        // the source was modified before the copy, so nothing is changed
+        let (endpoint, requests) =
+            serve_sequence(vec![HEAD, ("412 Precondition Failed", "", "")]).await;
+        let object = local_operator(&endpoint);
+        assert!(matches!(
+            object.move_object("move.txt", "moved.txt").await,
+            Err(error::OperationError::PreconditionFailed(_))
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn unit_test_54_blocking_upload_file() {
+        let server = tokio::runtime::Runtime::new().unwrap();
+        let (endpoint, requests) = server.block_on(serve_xml(""));
+        let object = blocking::Operator::new(local_operator(&endpoint), None);
+        let path = env::temp_dir().join("cf_r2_sdk_blocking_upload_file.txt");
+        std::fs::write(&path, b"Hello, World!").unwrap();
+
+        object
+            .upload_file("hello.txt", "text/plain", &path, None)
+            .unwrap();
+        {
+            let requests = requests.lock().unwrap();
+            assert_eq!(requests.len(), 1);
+            assert!(requests[0].starts_with("PUT /bucket/hello.txt"));
+            assert!(requests[0].to_lowercase().contains("content-length: 13"));
+        }
+
+        // the errors are returned before any request
+        assert!(matches!(
+            object.upload_file("hello.txt", "", &path, None),
+            Err(error::OperationError::InvalidContentType(_))
+        ));
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            object.upload_file("hello.txt", "text/plain", &path, None),
+            Err(error::OperationError::FileOpenError(_))
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn unit_test_55_upload_binary_with_options() {
+        use std::time::{Duration, SystemTime};
+
+        let (endpoint, requests) = serve_xml("").await;
+        let object = local_operator(&endpoint);
+        let options = options::UploadOptions::new()
+            .set_cache_control("public, max-age=31536000")
+            .set_expires(SystemTime::UNIX_EPOCH + Duration::from_secs(1_893_456_000));
+
+        object
+            .upload_binary_with_options("app.js", "text/javascript", b"Hello, World!", &options)
+            .await
+            .unwrap();
+        let request = requests.lock().unwrap()[0].to_lowercase();
+        assert!(request.contains("cache-control: public, max-age=31536000"));
+        assert!(request.contains("expires: tue, 01 jan 2030 00:00:00 gmt"));
+        assert!(request.contains("content-type: text/javascript"));
+    }
+
+    #[tokio::test]
+    async fn unit_test_56_builder_clear() {
+        let (endpoint, requests) = serve_xml("").await;
+        let template = Builder::new()
+            .set_bucket_name("bucket".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint(endpoint.clone())
+            .set_force_path_style(true)
+            .set_header("x-tenant", "a");
+
+        assert!(matches!(
+            template.clone().clear_bucket_name().create_client_result(),
+            Err(error::BuilderError::BucketNameNotSetError)
+        ));
+        assert!(matches!(
+            template.clone().clear_endpoint().create_client_result(),
+            Err(error::BuilderError::EndpointNotSetError)
+        ));
+        assert!(matches!(
+            template.clone().clear_credentials().create_client_result(),
+            Err(error::BuilderError::AccessKeyIdNotSetError)
+        ));
+        assert!(matches!(
+            template.clone().reset().create_client_result(),
+            Err(error::BuilderError::BucketNameNotSetError)
+        ));
+
+        let object = template
+            .clear_headers()
+            .set_header("x-tenant", "b")
+            .create_client_result()
+            .unwrap();
+        object.download("a.txt").await.unwrap();
+        let request = requests.lock().unwrap()[0].to_lowercase();
+        assert!(request.contains("x-tenant: b"));
+        assert!(!request.contains("x-tenant: a"));
+    }
+
+    #[tokio::test]
+    async fn unit_test_57_copy_object_verified_if_match() {
+        let (endpoint, requests) = serve_sequence(vec![
+            ("200 OK", "ETag: \"source\"\r\n", ""),
+            ("412 Precondition Failed", "", ""),
+        ])
+        .await;
+        let object = local_operator(&endpoint);
+
+        // the source was overwritten between the HEAD and the copy
+        assert!(matches!(
+            object
+                .copy_object_verified("source.txt", "copied.txt")
+                .await,
+            Err(error::OperationError::PreconditionFailed(_))
+        ));
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[1]
+            .to_lowercase()
+            .contains("x-amz-copy-source-if-match: \"source\""));
+    }
+
+    #[tokio::test]
+    async fn unit_test_58_listing_url_encoding() {
+        let (endpoint, requests) = serve_xml(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><Prefix>dir+1/</Prefix><Delimiter>/</Delimiter><EncodingType>url</EncodingType><KeyCount>2</KeyCount><IsTruncated>false</IsTruncated><Contents><Key>dir+1/100%25+a%2Bb+c.txt</Key><Size>5</Size></Contents><CommonPrefixes><Prefix>dir+1/sub+dir/</Prefix></CommonPrefixes></ListBucketResult>"#,
+        )
+        .await;
+        let object = local_operator(&endpoint);
+        let key = "dir 1/100% a+b c.txt";
+
+        // every listing decodes the keys the same way
+        assert_eq!(object.list_objects().await.unwrap(), [key]);
+        assert_eq!(
+            object.list_objects_detailed("dir 1/").await.unwrap()[0].key,
+            key
+        );
+        assert_eq!(
+            object.list_objects_page(10, None).await.unwrap().keys,
+            [key]
+        );
+        let level = object.list_level("dir 1/").await.unwrap();
+        assert_eq!(level.folders, ["dir 1/sub dir/"]);
+        assert_eq!(level.files[0].key, key);
+        let mut keys = Vec::new();
+        object
+            .for_each_object(None, |key| keys.push(key.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(keys, [key]);
+        assert_eq!(object.plan_delete_all().await.unwrap(), [key]);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 6);
+        assert!(requests
+            .iter()
+            .all(|request| request.contains("encoding-type=url")));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn unit_test_59_metrics_once_per_call() {
+        use metrics::{
+            Counter, CounterFn, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString,
+            Unit,
+        };
+        use std::sync::{Arc, Mutex};
+
+        type Increments = Arc<Mutex<Vec<(String, u64)>>>;
+
+        /// Record the `op` label of each counter increment.
+        struct RecordingRecorder(Increments);
+        struct RecordingCounter(String, Increments);
+
+        impl CounterFn for RecordingCounter {
+            fn increment(&self, value: u64) {
+                self.1.lock().unwrap().push((self.0.clone(), value));
+            }
+            fn absolute(&self, _: u64) {}
+        }
+
+        impl Recorder for RecordingRecorder {
+            fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+                let op = key
+                    .labels()
+                    .find(|label| label.key() == "op")
+                    .map(|label| label.value().to_string())
+                    .unwrap_or_default();
+                Counter::from_arc(Arc::new(RecordingCounter(op, self.0.clone())))
+            }
+            fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+                Gauge::noop()
+            }
+            fn register_histogram(&self, _: &Key, _: &Metadata<'_>) -> Histogram {
+                Histogram::noop()
+            }
+        }
+
+        let increments = Increments::default();
+        let recorder = RecordingRecorder(increments.clone());
+        metrics::with_local_recorder(&recorder, || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(async {
+                    let (endpoint, _) = serve_xml("Hello, World!").await;
+                    let object = local_operator(&endpoint);
+                    // download calls download_from, upload_binary calls upload_binary_to
+                    object.download("a.txt").await.unwrap();
+                    object
+                        .upload_binary("b.txt", "text/plain", b"Hello", None)
+                        .await
+                        .unwrap();
+                })
+        });
+        assert_eq!(
+            *increments.lock().unwrap(),
+            [
+                ("download".to_string(), 1),
+                ("upload_binary".to_string(), 1)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn unit_test_60_timeout_stalled_stream() {
+        use futures::StreamExt;
+        use std::time::Duration;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // send the headers and the first bytes of the body, then stall
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buffer = [0; 4096];
+                    let _ = stream.read(&mut buffer).await;
+                    let _ = stream
+                        .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\nHello")
+                        .await;
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                });
+            }
+        });
+        let object = local_operator(&endpoint).with_timeout(Duration::from_millis(300));
+
+        let result = tokio::time::timeout(Duration::from_secs(5), async {
+            let mut stream = Box::pin(object.download_stream("a.txt").await.unwrap());
+            assert_eq!(stream.next().await.unwrap().unwrap().as_ref(), b"Hello");
+            stream.next().await.unwrap()
+        })
+        .await
+        .expect("the stalled stream was not timed out");
+        assert!(matches!(result, Err(error::OperationError::Timeout(_))));
+
+        let result = tokio::time::timeout(Duration::from_secs(5), async {
+            let mut reader = object.get_range_stream("a.txt", 0, 100).await.unwrap();
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await
+        })
+        .await
+        .expect("the stalled reader was not timed out");
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::TimedOut);
+    }
+
+    #[tokio::test]
+    async fn unit_test_61_signing_region() {
+        let (endpoint, requests) = serve_xml("").await;
+        let builder = Builder::new()
+            .set_bucket_name("bucket".to_string())
+            .set_access_key_id("access_key_id".to_string())
+            .set_secret_access_key("secret_access_key".to_string())
+            .set_endpoint(endpoint)
+            .set_force_path_style(true)
+            .set_region("us-east-1".to_string());
+
+        // the region is used for signing by default
+        let object = builder.clone().create_client_result().unwrap();
+        object.download("a.txt").await.unwrap();
+        // only the credential scope uses the signing region
+        let object = builder
+            .set_signing_region("eu-west-1".to_string())
+            .create_client_result()
+            .unwrap();
+        object.download("a.txt").await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        let credential_scope = |request: &str| {
+            let authorization = request
+                .lines()
+                .find(|line| line.to_lowercase().starts_with("authorization:"))
+                .unwrap()
+                .to_string();
+            authorization
+                .split("Credential=access_key_id/")
+                .nth(1)
+                .and_then(|scope| scope.split(',').next())
+                .map(|scope| scope.split_once('/').unwrap().1.to_string())
+                .unwrap()
+        };
+        assert_eq!(credential_scope(&requests[0]), "us-east-1/s3/aws4_request");
+        assert_eq!(credential_scope(&requests[1]), "eu-west-1/s3/aws4_request");
+        // the endpoint is resolved with the region
+        assert!(requests[1].starts_with("GET /bucket/a.txt"));
+    }
+
+    #[tokio::test]
+    async fn unit_test_62_throttle_upload_body() {
+        use std::time::{Duration, Instant};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // read the whole request body before responding
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let received = std::sync::Arc::new(std::sync::Mutex::new(0));
+        let counter = received.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buffer = [0; 4096];
+                let header_end = loop {
+                    if let Some(end) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                        break end + 4;
+                    }
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => request.extend_from_slice(&buffer[..n]),
+                    }
+                };
+                let headers = String::from_utf8_lossy(&request[..header_end]).to_lowercase();
+                let content_length: usize = headers
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length:"))
+                    .map(|value| value.trim().parse().unwrap())
+                    .unwrap_or(0);
+                let mut body = request.len() - header_end;
+                while body < content_length {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => body += n,
+                    }
+                }
+                *counter.lock().unwrap() += body;
+                let _ = stream
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .await;
+            }
+        });
+        let object = local_operator(&endpoint).with_rate_limit(Some(100_000));
+
+        // the 200 KB body is sent in chunks of 64 KiB at 100 KB/s instead of at once
+        let start = Instant::now();
+        object
+            .upload_binary("a.bin", "application/octet-stream", &[0; 200_000], None)
+            .await
+            .unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(1500));
+        assert!(*received.lock().unwrap() >= 200_000);
+    }
+
+    #[cfg(feature = "decompress")]
+    #[test]
+    fn unit_test_63_decompress_members() {
+        use decompress::{decode, decode_limited};
+        use std::io::Write;
+
+        fn gzip(data: &[u8]) -> Vec<u8> {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data).unwrap();
+            encoder.finish().unwrap()
+        }
+
+        // every member of a multi-member gzip is decoded
+        let mut members = gzip(b"Hello, ");
+        members.extend(gzip(b"World!"));
+        assert_eq!(decode("gzip", members).unwrap(), b"Hello, World!");
+
+        // a corrupt CRC32 or ISIZE trailer is rejected
+        for offset in [8, 4] {
+            let mut corrupt = gzip(b"Hello, World!");
+            let len = corrupt.len();
+            corrupt[len - offset] ^= 0xff;
+            assert!(matches!(
+                decode("gzip", corrupt),
+                Err(error::OperationError::DecompressError(_))
+            ));
+        }
+
+        // br
+        let mut br = Vec::new();
+        {
+            let mut encoder = brotli::CompressorWriter::new(&mut br, 4096, 11, 22);
+            encoder.write_all(b"Hello, World!").unwrap();
+        }
+        assert_eq!(decode("br", br).unwrap(), b"Hello, World!");
+
+        // a body decoded beyond the limit is rejected
+        let bomb = gzip(&[0; 10_000]);
+        assert_eq!(
+            decode_limited("gzip", bomb.clone(), 10_000).unwrap().len(),
+            10_000
+        );
+        assert!(matches!(
+            decode_limited("gzip", bomb, 9_999),
+            Err(error::OperationError::DecompressError(_))
+        ));
+    }
+
+    #[cfg(feature = "wasm")]
+    #[tokio::test]
+    async fn unit_test_64_fetch_http_client() {
+        // the client used on WASM sends the requests and reads the responses of the SDK
+        let (endpoint, requests) = serve_sequence(vec![
+            (
+                "200 OK",
+                "ETag: \"65a8e27d8879283831b664bd8b7f0ad4\"\r\n",
+                "",
+            ),
+            ("200 OK", "", "Hello, World!"),
+            ("204 No Content", "", ""),
+        ])
+        .await;
+        let config = aws_sdk_s3::Config::builder()
+            .behavior_version_latest()
+            .http_client(wasm::FetchHttpClient::new())
+            .endpoint_url(&endpoint)
+            .region(aws_sdk_s3::config::Region::new("auto"))
+            .credentials_provider(aws_sdk_s3::config::Credentials::new(
+                "access_key_id",
+                "secret_access_key",
+                None,
+                None,
+                "test",
+            ))
+            .force_path_style(true)
+            .build();
+        let object =
+            operator::Operator::new("bucket".to_string(), aws_sdk_s3::Client::from_conf(config));
+
+        object
+            .upload_binary("a.txt", "text/plain", b"Hello, World!", None)
+            .await
+            .unwrap();
+        assert_eq!(object.download("a.txt").await.unwrap(), b"Hello, World!");
+        object.delete("a.txt").await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("PUT /bucket/a.txt?"));
+        assert!(requests[0].contains("authorization: AWS4-HMAC-SHA256"));
+        assert!(requests[1].starts_with("GET /bucket/a.txt?"));
+        assert!(requests[2].starts_with("DELETE /bucket/a.txt?"));
+    }
+
+    #[tokio::test]
+    async fn unit_test_65_sync_down_sibling_prefix() {
+        // `backup2/x.txt` shares the string prefix `backup` but is not under the directory `backup`
+        let (endpoint, requests) = serve_sequence(vec![
+            (
+                "200 OK",
+                "",
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><Prefix>backup/</Prefix><KeyCount>2</KeyCount><MaxKeys>1000</MaxKeys><IsTruncated>false</IsTruncated><Contents><Key>backup/a.txt</Key><LastModified>2025-01-01T00:00:00.000Z</LastModified><Size>5</Size></Contents><Contents><Key>backup2/x.txt</Key><LastModified>2025-01-01T00:00:00.000Z</LastModified><Size>5</Size></Contents></ListBucketResult>"#,
+            ),
+            ("200 OK", "", "Hello"),
+        ])
+        .await;
+        let object = local_operator(&endpoint);
+        let local_dir =
+            std::env::temp_dir().join(format!("cf_r2_sdk_unit_test_65_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&local_dir);
+
+        let report = object
+            .sync_down("backup", &local_dir, &options::SyncDownOptions::new())
+            .await
+            .unwrap();
+        assert_eq!(report.downloaded, 1);
+        assert_eq!(std::fs::read(local_dir.join("a.txt")).unwrap(), b"Hello");
+        assert!(!local_dir.join("2").exists());
+        assert!(!local_dir.join("x.txt").exists());
+        // no temporary file is left
+        assert_eq!(std::fs::read_dir(&local_dir).unwrap().count(), 1);
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].contains("prefix=backup%2F"));
+        assert_eq!(requests.len(), 2);
+        drop(requests);
+        std::fs::remove_dir_all(&local_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn unit_test_66_download_ranges_without_range_support() {
+        // the server ignores `Range` and always returns the whole file
+        let (endpoint, requests) = serve_sequence(vec![("200 OK", "", "Hello, World!")]).await;
+        let object = local_operator(&endpoint);
+
+        let parts = object
+            .download_ranges("file.txt", &[(7, 11), (0, 4), (12, 100)])
+            .await
+            .unwrap();
+        assert_eq!(
+            parts,
+            vec![b"World".to_vec(), b"Hello".to_vec(), b"!".to_vec()]
+        );
+        // the whole file is downloaded only once
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        let err = object
+            .download_ranges("file.txt", &[(0, 4), (13, 20)])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            error::OperationError::InvalidRange {
+                start: 13,
+                size: Some(13),
+                ..
+            }
+        ));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn unit_test_67_delete_stream_timeout() {
+        use futures::StreamExt;
+        use std::time::Duration;
+
+        // accept the connections without ever responding, so the listing stalls
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut streams = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                streams.push(stream);
+            }
+        });
+        let object = local_operator(&endpoint).with_timeout(Duration::from_millis(200));
+
+        let mut batches = std::pin::pin!(object.delete_prefix_stream("logs/"));
+        let batch = tokio::time::timeout(Duration::from_secs(5), batches.next())
+            .await
+            .unwrap();
+        assert!(matches!(
+            batch,
+            Some(Err(error::OperationError::Timeout(timeout))) if timeout == Duration::from_millis(200)
+        ));
+    }
+
+    #[tokio::test]
+    async fn unit_test_68_sync_up_streams_with_detected_type() {
+        let (endpoint, requests) = serve_sequence(vec![
+            (
+                "200 OK",
+                "",
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/"><Name>bucket</Name><Prefix>backup/</Prefix><KeyCount>0</KeyCount><MaxKeys>1000</MaxKeys><IsTruncated>false</IsTruncated></ListBucketResult>"#,
+            ),
+            ("200 OK", "", ""),
+        ])
+        .await;
+        let object = local_operator(&endpoint);
+        let local_dir =
+            std::env::temp_dir().join(format!("cf_r2_sdk_unit_test_68_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&local_dir);
+        std::fs::create_dir_all(&local_dir).unwrap();
+        let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR";
+        std::fs::write(local_dir.join("a.png"), png).unwrap();
+
+        let report = object.sync_up(&local_dir, "backup").await.unwrap();
+        assert_eq!(report.uploaded, 1);
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        let put = requests[1].to_ascii_lowercase();
+        assert!(put.starts_with("put /bucket/backup/a.png?"));
+        assert!(put.contains(&format!("content-length: {}", png.len())));
+        #[cfg(feature = "infer")]
+        assert!(put.contains("content-type: image/png"));
+        #[cfg(not(feature = "infer"))]
+        assert!(put.contains("content-type: application/octet-stream"));
+        drop(requests);
+        std::fs::remove_dir_all(&local_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn unit_test_69_upload_file_as_content_type() {
+        let (endpoint, requests) = serve_sequence(vec![("200 OK", "", "")]).await;
+        let object = local_operator(&endpoint);
+        let path =
+            std::env::temp_dir().join(format!("cf_r2_sdk_unit_test_69_{}.txt", std::process::id()));
+        std::fs::write(&path, b"Hello, World!").unwrap();
+
+        let key = object
+            .upload_file_as(&path, "backup", Some("text/plain"))
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let requests = requests.lock().unwrap();
+        let put = requests[0].to_ascii_lowercase();
+        assert!(put.starts_with(&format!("put /bucket/{}?", key)));
+        assert!(put.contains("content-type: text/plain"));
+        assert!(put.contains("content-length: 13"));
+    }
+
+    #[tokio::test]
+    async fn unit_test_70_upload_idempotent_multipart_etag() {
+        // the file already exists as a multipart upload of `Hello` in one part
+        let precondition_failed = (
+            "412 Precondition Failed",
+            "",
+            "<Error><Code>PreconditionFailed</Code></Error>",
+        );
+        let head = (
+            "200 OK",
+            "ETag: \"49c24cf3c5af9ba03cec39ee4aac4f77-1\"\r\n",
+            "",
+        );
+        let (endpoint, _) =
+            serve_sequence(vec![precondition_failed, head, precondition_failed, head]).await;
+        let object = local_operator(&endpoint);
+
+        object
+            .upload_binary_idempotent("a.txt", "text/plain", b"Hello", None)
+            .await
+            .unwrap();
+        let err = object
+            .upload_binary_idempotent("a.txt", "text/plain", b"World", None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, error::OperationError::PreconditionFailed(_)));
+    }
+
     //* Actions Test *//
     #[tokio::test]
     #[ignore]
-    async fn actions_test_1_upload_and_download_binary() -> Result<(), Error> {
+    async fn actions_test_1_upload_and_download_binary() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("test.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let bin = object.download("test.txt").await;
+        match bin {
+            Ok(bin) => assert_eq!(bin, b"Hello, World!"),
+            Err(e) => panic!("Error: {:?}", e),
+        }
+
+        object.delete("test.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_2_upload_and_download_file() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let file_path = "./data/sample.jpg";
+        object
+            .upload_file("sample.jpg", "image/jpeg", file_path, None)
+            .await?;
+
+        let bin = object.download("sample.jpg").await;
+
+        //read file from local
+        let mut file = File::open(file_path).await.expect("Failed to open file");
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)
+            .await
+            .expect("Failed to close file");
+
+        match bin {
+            Ok(bin) => assert_eq!(bin, buffer),
+            Err(e) => panic!("Error: {:?}", e),
+        }
+
+        object.delete("sample.jpg").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_3_upload_and_delete() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("text.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        object.delete("text.txt").await?;
+
+        let bin: Result<Vec<u8>, error::OperationError> = object.download("text.txt").await;
+        if bin.is_ok() {
+            panic!("Error: {:?}", bin)
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_4_list_objects() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let _data = object.list_objects().await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_5_get_range_stream() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("range.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let mut reader = object.get_range_stream("range.txt", 7, 5).await?;
+        let mut buffer = Vec::new();
+        reader
+            .read_to_end(&mut buffer)
+            .await
+            .expect("Failed to read range");
+        assert_eq!(buffer, b"World");
+
+        object.delete("range.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_6_delete_objects() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("delete_1.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+        object
+            .upload_binary("delete_2.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let mut result = object
+            .delete_objects(&["delete_1.txt", "delete_2.txt"])
+            .await?;
+        result.deleted.sort();
+        assert_eq!(result.deleted, vec!["delete_1.txt", "delete_2.txt"]);
+        assert!(result.errors.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_7_move_object() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("move.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        object.move_object("move.txt", "moved.txt").await?;
+
+        assert!(object.download("move.txt").await.is_err());
+        assert_eq!(object.download("moved.txt").await?, b"Hello, World!");
+
+        object.delete("moved.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_8_download_range() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("download_range.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let result = object
+            .download_range("download_range.txt", Some((7, 11)))
+            .await?;
+        assert!(result.is_partial);
+        assert_eq!(result.bytes, b"World");
+
+        let result = object.download_range("download_range.txt", None).await?;
+        assert!(!result.is_partial);
+        assert_eq!(result.bytes, b"Hello, World!");
+
+        object.delete("download_range.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_9_special_character_keys() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let file_name = "folder/名前 (1)+copy.txt";
+        let copied_file_name = "folder/名前 (2)+copy.txt";
+        object
+            .upload_binary(file_name, "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let file_names = object.list_objects().await?;
+        assert!(file_names.iter().any(|name| name == file_name));
+
+        assert_eq!(object.download(file_name).await?, b"Hello, World!");
+
+        let copied = object.copy_object(file_name, copied_file_name).await?;
+        assert_eq!(copied.size, 13);
+        assert_eq!(
+            copied.e_tag,
+            object.head_object(copied_file_name).await?.e_tag
+        );
+        assert_eq!(object.download(copied_file_name).await?, b"Hello, World!");
+
+        object.delete(file_name).await?;
+        object.delete(copied_file_name).await?;
+        assert!(object.download(file_name).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_10_head_object() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("head_object.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let metadata = object.head_object("head_object.txt").await?;
+        assert_eq!(metadata.size, 13);
+        assert_eq!(metadata.content_type.as_deref(), Some("text/plain"));
+        assert_eq!(metadata.cache_control.as_deref(), Some("no-cache"));
+
+        object.delete("head_object.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_11_website_redirect_location() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let options = options::UploadOptions::new().set_website_redirect_location("/index.html");
+        object
+            .upload_file_streaming(
+                "website_redirect_location.jpg",
+                "image/jpeg",
+                "data/sample.jpg",
+                &options,
+            )
+            .await?;
+
+        let metadata = object.head_object("website_redirect_location.jpg").await?;
+        assert_eq!(
+            metadata.website_redirect_location.as_deref(),
+            Some("/index.html")
+        );
+
+        object.delete("website_redirect_location.jpg").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_12_dry_run() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("dry_run/a.txt", "text/plain", b"a", None)
+            .await?;
+        object
+            .upload_binary("dry_run/b.txt", "text/plain", b"b", None)
+            .await?;
+
+        let mut planned = object.plan_delete_prefix("dry_run/").await?;
+        planned.sort();
+        assert_eq!(planned, vec!["dry_run/a.txt", "dry_run/b.txt"]);
+        // nothing is deleted by the plan
+        assert_eq!(object.download("dry_run/a.txt").await?, b"a");
+
+        assert_eq!(object.delete_prefix("dry_run/").await?, 2);
+        assert!(object.plan_delete_prefix("dry_run/").await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_13_list_objects_limited() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        for i in 0..3 {
+            object
+                .upload_binary(&format!("limited/{}.txt", i), "text/plain", b"a", None)
+                .await?;
+        }
+
+        let list = object.list_objects_limited(2).await?;
+        assert_eq!(list.file_names.len(), 2);
+        assert!(list.has_more);
+
+        for i in 0..3 {
+            object.delete(&format!("limited/{}.txt", i)).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_14_head_objects() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("head_objects.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let results = object
+            .head_objects(&["head_objects.txt", "head_objects_missing.txt"])
+            .await?;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "head_objects.txt");
+        assert_eq!(results[0].1.as_ref().unwrap().size, 13);
+        assert_eq!(results[1].0, "head_objects_missing.txt");
+        assert!(results[1].1.is_err());
+
+        object.delete("head_objects.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_15_copy_object_with_options() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary(
+                "copy_with_options.txt",
+                "text/plain",
+                b"Hello, World!",
+                None,
+            )
+            .await?;
+
+        let options = options::UploadOptions::new().set_cache_control("max-age=31536000");
+        object
+            .copy_object_with_options(
+                "copy_with_options.txt",
+                "copy_with_options_copied.txt",
+                &options,
+            )
+            .await?;
+
+        let metadata = object.head_object("copy_with_options_copied.txt").await?;
+        assert_eq!(metadata.cache_control.as_deref(), Some("max-age=31536000"));
+        assert_eq!(metadata.content_type.as_deref(), Some("text/plain"));
+
+        object.delete("copy_with_options.txt").await?;
+        object.delete("copy_with_options_copied.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_16_list_level() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("list_level/sub/a.txt", "text/plain", b"a", None)
+            .await?;
+        object
+            .upload_binary("list_level/b.txt", "text/plain", b"bb", None)
+            .await?;
+
+        let level = object.list_level("list_level/").await?;
+        assert_eq!(level.folders, vec!["list_level/sub/"]);
+        assert_eq!(level.files.len(), 1);
+        assert_eq!(level.files[0].key, "list_level/b.txt");
+        assert_eq!(level.files[0].size, 2);
+
+        object.delete("list_level/sub/a.txt").await?;
+        object.delete("list_level/b.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_17_sync_down() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("sync_down/a.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+        let last_modified = object.head_object("sync_down/a.txt").await?.last_modified;
+
+        let local_dir = env::temp_dir().join("cf_r2_sdk_sync_down");
+        let options = options::SyncDownOptions::new().set_preserve_mtime(true);
+        let report = object.sync_down("sync_down", &local_dir, &options).await?;
+        assert_eq!(report.downloaded, 1);
+
+        let path = local_dir.join("a.txt");
+        assert_eq!(std::fs::read(&path).unwrap(), b"Hello, World!");
+        assert_eq!(
+            std::fs::metadata(&path).unwrap().modified().ok(),
+            last_modified
+        );
+
+        // the second sync skips the up-to-date file
+        let report = object.sync_down("sync_down", &local_dir, &options).await?;
+        assert_eq!(report.skipped, 1);
+
+        std::fs::remove_dir_all(&local_dir).unwrap();
+        object.delete("sync_down/a.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_18_upload_reader() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let reader: &[u8] = b"Hello, World!";
+        object
+            .upload_reader("upload_reader.txt", "text/plain", reader, None)
+            .await?;
+
+        let bin = object.download("upload_reader.txt").await?;
+        assert_eq!(bin, b"Hello, World!");
+
+        object.delete("upload_reader.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_19_list_objects_page() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        for i in 0..3 {
+            object
+                .upload_binary(&format!("list_page/{}.txt", i), "text/plain", b"a", None)
+                .await?;
+        }
+
+        let mut keys = Vec::new();
+        let mut token: Option<String> = None;
+        loop {
+            let page = object.list_objects_page(1, token.as_deref()).await?;
+            assert_eq!(page.key_count, page.keys.len());
+            keys.extend(page.keys);
+            if !page.is_truncated {
+                break;
+            }
+            token = page.next_token;
+        }
+        for i in 0..3 {
+            assert!(keys.contains(&format!("list_page/{}.txt", i)));
+        }
+
+        for i in 0..3 {
+            object.delete(&format!("list_page/{}.txt", i)).await?;
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_20_append() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object.append("append.log", b"first\n").await?;
+        object.append("append.log", b"second\n").await?;
+
+        let bin = object.download("append.log").await?;
+        assert_eq!(bin, b"first\nsecond\n");
+
+        object.delete("append.log").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_21_download_capped() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("download_capped.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let bin = object.download_capped("download_capped.txt", 13).await?;
+        assert_eq!(bin, b"Hello, World!");
+        assert!(matches!(
+            object.download_capped("download_capped.txt", 12).await,
+            Err(error::OperationError::TooLarge {
+                size: 13,
+                limit: 12
+            })
+        ));
+
+        object.delete("download_capped.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_22_upload_binary_idempotent() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary_idempotent("upload_idempotent.txt", "text/plain", b"Hello", None)
+            .await?;
+        // retrying with the same content succeeds
+        object
+            .upload_binary_idempotent("upload_idempotent.txt", "text/plain", b"Hello", None)
+            .await?;
+        // different content does not overwrite the file
+        assert!(matches!(
+            object
+                .upload_binary_idempotent("upload_idempotent.txt", "text/plain", b"World", None)
+                .await,
+            Err(error::OperationError::PreconditionFailed(_))
+        ));
+        assert_eq!(object.download("upload_idempotent.txt").await?, b"Hello");
+
+        object.delete("upload_idempotent.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_23_upload_from_stream() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        // 20 MiB in chunks of 1 MiB: two full parts of 8 MiB and the last part of 4 MiB
+        let data: Vec<u8> = (0..20 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        let chunks: Vec<Result<bytes::Bytes, std::io::Error>> = data
+            .chunks(1024 * 1024)
+            .map(|chunk| Ok(bytes::Bytes::copy_from_slice(chunk)))
+            .collect();
+        object
+            .upload_from_stream(
+                "upload_from_stream.bin",
+                "application/octet-stream",
+                futures::stream::iter(chunks),
+            )
+            .await?;
+        assert_eq!(object.download("upload_from_stream.bin").await?, data);
+
+        object.delete("upload_from_stream.bin").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_24_download_stream() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        use futures::StreamExt;
+
+        object
+            .upload_binary("download_stream.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+
+        let stream = object.download_stream("download_stream.txt").await?;
+        // the stream does not borrow the operator, so it can be moved to another task
+        let chunks: Vec<_> = tokio::spawn(stream.collect::<Vec<_>>()).await.unwrap();
+        let mut bin = Vec::new();
+        for chunk in chunks {
+            bin.extend_from_slice(&chunk?);
+        }
+        assert_eq!(bin, b"Hello, World!");
+
+        object.delete("download_stream.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_25_copy_object_if_match() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("copy_if_match.txt", "text/plain", b"Hello", None)
+            .await?;
+        let e_tag = object
+            .head_object("copy_if_match.txt")
+            .await?
+            .e_tag
+            .expect("ETag not found.");
+
+        let copied = object
+            .copy_object_if_match("copy_if_match.txt", "copy_if_match_copied.txt", &e_tag)
+            .await?;
+        assert_eq!(copied.size, 5);
+
+        // the source was changed after the ETag was taken
+        object
+            .upload_binary("copy_if_match.txt", "text/plain", b"World", None)
+            .await?;
+        assert!(matches!(
+            object
+                .copy_object_if_match("copy_if_match.txt", "copy_if_match_copied.txt", &e_tag)
+                .await,
+            Err(error::OperationError::PreconditionFailed(_))
+        ));
+        assert_eq!(object.download("copy_if_match_copied.txt").await?, b"Hello");
+
+        object.delete("copy_if_match.txt").await?;
+        object.delete("copy_if_match_copied.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_26_download_ranges() -> Result<(), Error> {
         // insert a environment variable
         let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
         let endpoint_url: String =
@@ -464,22 +4924,111 @@ mod tests {
             .create_client_result()?;
 
         object
-            .upload_binary("test.txt", "text/plain", b"Hello, World!", None)
+            .upload_binary("download_ranges.txt", "text/plain", b"Hello, World!", None)
             .await?;
 
-        let bin = object.download("test.txt").await;
-        match bin {
-            Ok(bin) => assert_eq!(bin, b"Hello, World!"),
-            Err(e) => panic!("Error: {:?}", e),
+        let ranges = object
+            .download_ranges("download_ranges.txt", &[(7, 11), (0, 4), (12, 100)])
+            .await?;
+        assert_eq!(
+            ranges,
+            vec![b"World".to_vec(), b"Hello".to_vec(), b"!".to_vec()]
+        );
+        assert!(object
+            .download_ranges("download_ranges.txt", &[])
+            .await?
+            .is_empty());
+
+        object.delete("download_ranges.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_27_bucket_cors() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let original = object.get_bucket_cors().await?;
+
+        let rules = vec![object::CorsRule {
+            allowed_origins: vec!["https://example.com".to_string()],
+            allowed_methods: vec!["GET".to_string()],
+            allowed_headers: vec!["*".to_string()],
+            expose_headers: vec!["ETag".to_string()],
+            max_age_seconds: Some(3600),
+        }];
+        object.put_bucket_cors(&rules).await?;
+        assert_eq!(object.get_bucket_cors().await?, rules);
+
+        object.delete_bucket_cors().await?;
+        assert!(object.get_bucket_cors().await?.is_empty());
+
+        // restore the original rules
+        if !original.is_empty() {
+            object.put_bucket_cors(&original).await?;
         }
+        Ok(())
+    }
 
-        object.delete("test.txt").await?;
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_28_bucket_lifecycle() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let original = object.get_bucket_lifecycle().await?;
+
+        let rules = vec![object::LifecycleRule::new("test-expire-tmp")
+            .set_prefix("tmp/")
+            .set_expiration_days(7)
+            .set_abort_incomplete_multipart_upload_days(1)];
+        object.put_bucket_lifecycle(&rules).await?;
+        let stored = object.get_bucket_lifecycle().await?;
+        assert!(stored.contains(&rules[0]));
+
+        // restore the original rules
+        if original.is_empty() {
+            object.delete_bucket_lifecycle().await?;
+        } else {
+            object.put_bucket_lifecycle(&original).await?;
+        }
         Ok(())
     }
 
     #[tokio::test]
     #[ignore]
-    async fn actions_test_2_upload_and_download_file() -> Result<(), Error> {
+    async fn actions_test_29_sniff() -> Result<(), Error> {
         // insert a environment variable
         let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
         let endpoint_url: String =
@@ -498,32 +5047,115 @@ mod tests {
             .set_region(region)
             .create_client_result()?;
 
-        let file_path = "./data/sample.jpg";
         object
-            .upload_file("sample.jpg", "image/jpeg", file_path, None)
+            .upload_binary("sniff.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+        object
+            .upload_binary("sniff_empty.txt", "text/plain", b"", None)
             .await?;
 
-        let bin = object.download("sample.jpg").await;
+        assert_eq!(object.sniff("sniff.txt", 5).await?, b"Hello");
+        assert_eq!(object.sniff("sniff.txt", 100).await?, b"Hello, World!");
+        assert!(object.sniff("sniff.txt", 0).await?.is_empty());
+        assert!(object.sniff("sniff_empty.txt", 5).await?.is_empty());
 
-        //read file from local
-        let mut file = File::open(file_path).await.expect("Failed to open file");
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)
-            .await
-            .expect("Failed to close file");
+        object.delete("sniff.txt").await?;
+        object.delete("sniff_empty.txt").await?;
+        Ok(())
+    }
 
-        match bin {
-            Ok(bin) => assert_eq!(bin, buffer),
-            Err(e) => panic!("Error: {:?}", e),
-        }
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_30_list_objects_detailed() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
 
-        object.delete("sample.jpg").await?;
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("list_objects_detailed/a.txt", "text/plain", b"a", None)
+            .await?;
+
+        let files = object
+            .list_objects_detailed("list_objects_detailed/")
+            .await?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].key, "list_objects_detailed/a.txt");
+        assert_eq!(files[0].size, 1);
+
+        object.delete("list_objects_detailed/a.txt").await?;
         Ok(())
     }
 
     #[tokio::test]
     #[ignore]
-    async fn actions_test_3_upload_and_delete() -> Result<(), Error> {
+    async fn actions_test_31_sync_up_with_options() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let local_dir = env::temp_dir().join("cf_r2_sdk_sync_up_with_options");
+        std::fs::create_dir_all(&local_dir).unwrap();
+        std::fs::write(local_dir.join("a.txt"), b"Hello, World!").unwrap();
+
+        let options = options::SyncUpOptions::new().set_compare_content(true);
+        let report = object
+            .sync_up_with_options(&local_dir, "sync_up_with_options", &options)
+            .await?;
+        assert_eq!(report.uploaded, 1);
+
+        // the same content is skipped
+        let report = object
+            .sync_up_with_options(&local_dir, "sync_up_with_options", &options)
+            .await?;
+        assert_eq!(report.skipped, 1);
+
+        // a change keeping the size is uploaded
+        std::fs::write(local_dir.join("a.txt"), b"Hello, world!").unwrap();
+        let report = object
+            .sync_up_with_options(&local_dir, "sync_up_with_options", &options)
+            .await?;
+        assert_eq!(report.uploaded, 1);
+        assert_eq!(
+            object.download("sync_up_with_options/a.txt").await?,
+            b"Hello, world!"
+        );
+
+        object.delete("sync_up_with_options/a.txt").await?;
+        std::fs::remove_dir_all(&local_dir).unwrap();
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_32_download_to_file() -> Result<(), Error> {
         // insert a environment variable
         let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
         let endpoint_url: String =
@@ -543,21 +5175,23 @@ mod tests {
             .create_client_result()?;
 
         object
-            .upload_binary("text.txt", "text/plain", b"Hello, World!", None)
+            .upload_binary("download_to_file.txt", "text/plain", b"Hello, World!", None)
             .await?;
 
-        object.delete("text.txt").await?;
+        let path = env::temp_dir().join("cf_r2_sdk_download_to_file.txt");
+        object
+            .download_to_file("download_to_file.txt", &path)
+            .await?;
+        assert_eq!(std::fs::read(&path).unwrap(), b"Hello, World!");
 
-        let bin: Result<Vec<u8>, error::OperationError> = object.download("text.txt").await;
-        if bin.is_ok() {
-            panic!("Error: {:?}", bin)
-        }
+        object.delete("download_to_file.txt").await?;
+        std::fs::remove_file(&path).unwrap();
         Ok(())
     }
 
     #[tokio::test]
     #[ignore]
-    async fn actions_test_4_list_objects() -> Result<(), Error> {
+    async fn actions_test_33_upload_and_url() -> Result<(), Error> {
         // insert a environment variable
         let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
         let endpoint_url: String =
@@ -576,8 +5210,173 @@ mod tests {
             .set_region(region)
             .create_client_result()?;
 
-        let _data = object.list_objects().await?;
+        let url = object
+            .upload_and_url("upload_and_url.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+        assert!(url.ends_with("/upload_and_url.txt"));
+        assert_eq!(
+            object.download("upload_and_url.txt").await?,
+            b"Hello, World!"
+        );
+
+        object.delete("upload_and_url.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_34_checksum_algorithm() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let options = options::UploadOptions::new()
+            .set_checksum_algorithm(options::ChecksumAlgorithm::Sha256);
+        object
+            .upload_file_streaming(
+                "checksum_algorithm.jpg",
+                "image/jpeg",
+                "./data/sample.jpg",
+                &options,
+            )
+            .await?;
+        assert_eq!(
+            object.download("checksum_algorithm.jpg").await?,
+            std::fs::read("./data/sample.jpg").unwrap()
+        );
+
+        object.delete("checksum_algorithm.jpg").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_35_verify() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("verify.txt", "text/plain", b"Hello, World!", None)
+            .await?;
+        assert!(object.verify("verify.txt").await?);
+
+        // a multipart upload has a composite ETag
+        let data: Vec<u8> = (0..9 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        let chunks = data
+            .chunks(1024 * 1024)
+            .map(|chunk| Ok::<_, std::io::Error>(bytes::Bytes::copy_from_slice(chunk)))
+            .collect::<Vec<_>>();
+        object
+            .upload_from_stream(
+                "verify.bin",
+                "application/octet-stream",
+                futures::stream::iter(chunks),
+            )
+            .await?;
+        assert!(object.verify("verify.bin").await?);
+
+        object.delete("verify.txt").await?;
+        object.delete("verify.bin").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_36_prefix_stats() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        object
+            .upload_binary("prefix_stats/a.txt", "text/plain", b"a", None)
+            .await?;
+        object
+            .upload_binary("prefix_stats/b.txt", "text/plain", b"bb", None)
+            .await?;
+
+        let stats = object.prefix_stats("prefix_stats/").await?;
+        assert_eq!(stats.object_count, 2);
+        assert_eq!(stats.total_size, 3);
+
+        object.delete("prefix_stats/a.txt").await?;
+        object.delete("prefix_stats/b.txt").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn actions_test_37_upload_file_as() -> Result<(), Error> {
+        // insert a environment variable
+        let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        let endpoint_url: String =
+            env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        let access_key_id: String =
+            env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        let secret_access_key: String =
+            env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        let region: String = env::var("REGION").expect("REGION not found in .env file.");
+
+        let object = Builder::new()
+            .set_bucket_name(bucket_name)
+            .set_access_key_id(access_key_id)
+            .set_secret_access_key(secret_access_key)
+            .set_endpoint(endpoint_url)
+            .set_region(region)
+            .create_client_result()?;
+
+        let key = object
+            .upload_file_as("./data/sample.jpg", "upload_file_as", None)
+            .await?;
+        assert_eq!(key, "upload_file_as/data/sample.jpg");
+        assert_eq!(
+            object.download(&key).await?,
+            std::fs::read("./data/sample.jpg").unwrap()
+        );
 
+        object.delete(&key).await?;
         Ok(())
     }
 }
@@ -0,0 +1,467 @@
+use crate::{
+    error::{OperationError, R2Error},
+    object::MultipartUpload,
+    operator::{
+        check_content_type, check_etag, md5_digest, md5_etag, multipart_etag, unquote_etag,
+        Operator, MAX_CONCURRENCY,
+    },
+};
+use aws_sdk_s3::{
+    primitives::ByteStream,
+    types::{CompletedMultipartUpload, CompletedPart, RequestPayer},
+};
+use futures::{Stream, StreamExt, TryStreamExt};
+use std::time::{Duration, SystemTime};
+
+/// Largest body accepted by a single `PutObject` request (5 GiB).
+pub(crate) const MAX_SINGLE_PUT_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// Smallest size of each part of a multipart upload made by this crate (64 MiB).
+pub(crate) const MULTIPART_PART_SIZE: usize = 64 * 1024 * 1024;
+
+/// Size of each part of a multipart upload from a stream of chunks (8 MiB).
+pub(crate) const STREAM_PART_SIZE: usize = 8 * 1024 * 1024;
+
+/// Number of parts uploaded at once by a streaming upload, bounding the memory use.
+pub(crate) const STREAMING_CONCURRENCY: usize = 2;
+
+/// Maximum number of parts of a multipart upload.
+const MULTIPART_MAX_PARTS: usize = 10_000;
+
+impl Operator {
+    pub async fn list_multipart_uploads(&self) -> Result<Vec<MultipartUpload>, OperationError> {
+        //! Get the in-progress multipart uploads of the R2 bucket.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // get in-progress multipart uploads
+        //!    let uploads = object.list_multipart_uploads().await?;
+        //!
+        //!    for upload in uploads {
+        //!        println!("{} {}", upload.key, upload.upload_id);
+        //!    }
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("list_multipart_uploads", async {
+            let mut uploads = Vec::new();
+            let mut key_marker: Option<String> = None;
+            let mut upload_id_marker: Option<String> = None;
+            loop {
+                let output = match self
+                    .client
+                    .list_multipart_uploads()
+                    .set_request_payer(self.request_payer())
+                    .bucket(&self.bucket_name)
+                    .set_key_marker(key_marker.take())
+                    .set_upload_id_marker(upload_id_marker.take())
+                    .send()
+                    .await
+                {
+                    Ok(output) => output,
+                    Err(err) => {
+                        return Err(OperationError::AWSSdkS3ListMultipartUploadsError(
+                            err.into(),
+                        ))
+                    }
+                };
+                for upload in output.uploads() {
+                    if let (Some(key), Some(upload_id)) = (upload.key(), upload.upload_id()) {
+                        uploads.push(MultipartUpload {
+                            key: key.to_owned(),
+                            upload_id: upload_id.to_owned(),
+                            initiated: upload
+                                .initiated()
+                                .and_then(|initiated| SystemTime::try_from(*initiated).ok()),
+                        });
+                    }
+                }
+                if !output.is_truncated().unwrap_or(false) {
+                    break;
+                }
+                key_marker = output.next_key_marker().map(str::to_owned);
+                upload_id_marker = output.next_upload_id_marker().map(str::to_owned);
+                if key_marker.is_none() && upload_id_marker.is_none() {
+                    break;
+                }
+            }
+            Ok(uploads)
+        })
+        .await
+    }
+
+    pub async fn abort_multipart_upload(
+        &self,
+        file_name: &str,
+        upload_id: &str,
+    ) -> Result<(), OperationError> {
+        //! Abort an in-progress multipart upload and delete its uploaded parts.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // abort all in-progress multipart uploads of a file
+        //!    for upload in object.list_multipart_uploads().await? {
+        //!        if upload.key == "doctest_multipart_abort_multipart_upload.bin" {
+        //!            object
+        //!                .abort_multipart_upload(&upload.key, &upload.upload_id)
+        //!                .await?;
+        //!        }
+        //!    }
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("abort_multipart_upload", async {
+            match self
+                .client
+                .abort_multipart_upload()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .upload_id(upload_id)
+                .send()
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(err) => Err(OperationError::AWSSdkS3AbortMultipartUploadError(
+                    err.into(),
+                )),
+            }
+        })
+        .await
+    }
+
+    pub async fn abort_incomplete_multipart_uploads(
+        &self,
+        older_than: Duration,
+    ) -> Result<usize, OperationError> {
+        //! Abort the in-progress multipart uploads initiated more than `older_than` ago.
+        //!
+        //! Parts of abandoned multipart uploads still incur storage charges,
+        //! so this is useful for a periodic cleanup.
+        //! Uploads without an initiation time are kept.
+        //! Return the number of aborted uploads.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use std::time::Duration;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // abort multipart uploads older than a day
+        //!    let count = object
+        //!        .abort_incomplete_multipart_uploads(Duration::from_secs(60 * 60 * 24))
+        //!        .await?;
+        //!
+        //!    println!("{} uploads were aborted.", count);
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("abort_incomplete_multipart_uploads", async {
+            let now = SystemTime::now();
+            let mut count = 0;
+            for upload in self.list_multipart_uploads().await? {
+                let stale = upload
+                    .initiated
+                    .and_then(|initiated| now.duration_since(initiated).ok())
+                    .is_some_and(|age| age > older_than);
+                if stale {
+                    self.abort_multipart_upload(&upload.key, &upload.upload_id)
+                        .await?;
+                    count += 1;
+                }
+            }
+            Ok(count)
+        })
+        .await
+    }
+
+    /// Upload `parts` in order with a multipart upload, sending up to `concurrency` parts at once,
+    /// and return the ETag of the file.
+    ///
+    /// The upload is aborted if a part fails, and aborted in the background if the future is dropped.
+    pub(crate) async fn upload_multipart<S>(
+        &self,
+        bucket_name: &str,
+        file_name: &str,
+        mime_type: &str,
+        cache_control: Option<&str>,
+        parts: S,
+        concurrency: usize,
+    ) -> Result<Option<String>, OperationError>
+    where
+        S: Stream<Item = Result<Vec<u8>, OperationError>>,
+    {
+        check_content_type(mime_type)?;
+        let upload_id = match self
+            .client
+            .create_multipart_upload()
+            .set_request_payer(self.request_payer())
+            .bucket(bucket_name)
+            .key(file_name)
+            .content_type(mime_type)
+            .cache_control(cache_control.unwrap_or("no-cache"))
+            .send()
+            .await
+        {
+            Ok(output) => match output.upload_id() {
+                Some(upload_id) => upload_id.to_owned(),
+                None => {
+                    return Err(OperationError::AWSSdkS3CreateMultipartUploadError(
+                        R2Error::new("The response has no upload id."),
+                    ))
+                }
+            },
+            Err(err) => {
+                return Err(OperationError::AWSSdkS3CreateMultipartUploadError(
+                    err.into(),
+                ))
+            }
+        };
+        let mut guard = AbortOnDrop {
+            client: self.client.clone(),
+            bucket_name: bucket_name.to_string(),
+            file_name: file_name.to_string(),
+            upload_id: upload_id.clone(),
+            request_payer: self.request_payer(),
+            armed: true,
+        };
+
+        let result = async {
+            let upload_id = upload_id.as_str();
+            let parts: Vec<(CompletedPart, Option<[u8; 16]>)> = parts
+                .zip(futures::stream::iter(1..))
+                .map(|(part, part_number)| async move {
+                    self.upload_part(bucket_name, file_name, upload_id, part_number, part?)
+                        .await
+                })
+                .buffered(concurrency)
+                .try_collect()
+                .await?;
+            let (parts, digests): (Vec<_>, Vec<_>) = parts.into_iter().unzip();
+            let expected = match self.verify_integrity {
+                true => digests
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .map(|digests| multipart_etag(&digests)),
+                false => None,
+            };
+            match self
+                .client
+                .complete_multipart_upload()
+                .set_request_payer(self.request_payer())
+                .bucket(bucket_name)
+                .key(file_name)
+                .upload_id(upload_id)
+                .multipart_upload(
+                    CompletedMultipartUpload::builder()
+                        .set_parts(Some(parts))
+                        .build(),
+                )
+                .send()
+                .await
+            {
+                Ok(output) => {
+                    check_etag(expected, output.e_tag())?;
+                    Ok(output.e_tag().map(unquote_etag))
+                }
+                Err(err) => Err(OperationError::AWSSdkS3CompleteMultipartUploadError(
+                    err.into(),
+                )),
+            }
+        }
+        .await;
+
+        guard.armed = false;
+        if result.is_err() {
+            // the original error is more useful than a failure of the abort
+            let _ = self
+                .client
+                .abort_multipart_upload()
+                .set_request_payer(self.request_payer())
+                .bucket(bucket_name)
+                .key(file_name)
+                .upload_id(&upload_id)
+                .send()
+                .await;
+        }
+        result
+    }
+
+    /// Upload `binary` with a multipart upload in parts of [part_size], and return the ETag.
+    pub(crate) async fn upload_binary_multipart(
+        &self,
+        bucket_name: &str,
+        file_name: &str,
+        mime_type: &str,
+        binary: &[u8],
+        cache_control: Option<&str>,
+    ) -> Result<Option<String>, OperationError> {
+        let part_size = part_size(binary.len());
+        // Map owned offsets instead of `binary.chunks()`, so that the future stays `Send`.
+        let parts = (0..binary.len())
+            .step_by(part_size)
+            .map(move |start| Ok(binary[start..binary.len().min(start + part_size)].to_vec()));
+        self.upload_multipart(
+            bucket_name,
+            file_name,
+            mime_type,
+            cache_control,
+            futures::stream::iter(parts),
+            MAX_CONCURRENCY,
+        )
+        .await
+    }
+
+    /// Upload `chunk` as the part `part_number` of the multipart upload `upload_id`.
+    ///
+    /// The MD5 digest of `chunk` is also returned when the integrity is verified.
+    async fn upload_part(
+        &self,
+        bucket_name: &str,
+        file_name: &str,
+        upload_id: &str,
+        part_number: i32,
+        chunk: Vec<u8>,
+    ) -> Result<(CompletedPart, Option<[u8; 16]>), OperationError> {
+        let digest = self.verify_integrity.then(|| md5_digest(&chunk));
+        match self
+            .client
+            .upload_part()
+            .set_request_payer(self.request_payer())
+            .bucket(bucket_name)
+            .key(file_name)
+            .upload_id(upload_id)
+            .part_number(part_number)
+            .body(self.throttled_body(ByteStream::from(chunk)))
+            .send()
+            .await
+        {
+            Ok(output) => {
+                check_etag(digest.as_ref().map(md5_etag), output.e_tag())?;
+                let part = CompletedPart::builder()
+                    .part_number(part_number)
+                    .set_e_tag(output.e_tag().map(str::to_owned))
+                    .build();
+                Ok((part, digest))
+            }
+            Err(err) => Err(OperationError::AWSSdkS3UploadPartError(err.into())),
+        }
+    }
+}
+
+/// Size of each part so that `len` bytes fit in [MULTIPART_MAX_PARTS] parts.
+pub(crate) fn part_size(len: usize) -> usize {
+    MULTIPART_PART_SIZE.max(len.div_ceil(MULTIPART_MAX_PARTS))
+}
+
+/// Abort a multipart upload in the background when dropped while armed,
+/// e.g. when the upload is cancelled.
+struct AbortOnDrop {
+    client: aws_sdk_s3::Client,
+    bucket_name: String,
+    file_name: String,
+    upload_id: String,
+    request_payer: Option<RequestPayer>,
+    armed: bool,
+}
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let request = self
+                .client
+                .abort_multipart_upload()
+                .set_request_payer(self.request_payer.clone())
+                .bucket(&self.bucket_name)
+                .key(&self.file_name)
+                .upload_id(&self.upload_id);
+            handle.spawn(async move {
+                let _ = request.send().await;
+            });
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::time::{Duration, SystemTime};
+
+/// A downloaded file with the headers returned by R2.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DownloadedObject {
+    /// Binary data of the file.
+    pub body: Vec<u8>,
+    /// `Content-Type` header of the response.
+    pub content_type: Option<String>,
+    /// `Content-Disposition` header of the response.
+    pub content_disposition: Option<String>,
+    /// `Cache-Control` header of the response.
+    pub cache_control: Option<String>,
+}
+
+/// Metadata of a file, returned by `HeadObject`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObjectMetadata {
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// `Content-Type` of the file.
+    pub content_type: Option<String>,
+    /// `Cache-Control` of the file.
+    pub cache_control: Option<String>,
+    /// ETag of the file, without the surrounding double quotes (e.g. the hex MD5 for a single part upload).
+    pub e_tag: Option<String>,
+    /// Time when the file was last modified.
+    pub last_modified: Option<SystemTime>,
+    /// Lifecycle expiration of the file (`x-amz-expiration` header), if a lifecycle rule applies.
+    ///
+    /// e.g. `expiry-date="Fri, 23 Dec 2012 00:00:00 GMT", rule-id="picture-deletion-rule"`
+    pub expiration: Option<String>,
+    /// Redirect location of the file (`x-amz-website-redirect-location` header), if set.
+    pub website_redirect_location: Option<String>,
+    /// Restore status of an archived file (`x-amz-restore` header), if the backend provides it.
+    pub restore_status: Option<RestoreStatus>,
+}
+
+/// A downloaded file that may only be a part of the object.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DownloadResult {
+    /// Binary data of the response body.
+    pub bytes: Vec<u8>,
+    /// Whether the response is partial content (`206 Partial Content`).
+    ///
+    /// This is `false` when the server ignored the requested range and returned the whole object.
+    pub is_partial: bool,
+    /// `Content-Range` header of the response.
+    pub content_range: Option<String>,
+}
+
+/// Result of a batch deletion.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteObjectsResult {
+    /// File names (keys) that were deleted.
+    pub deleted: Vec<String>,
+    /// File names (keys) that could not be deleted, with the error message.
+    pub errors: Vec<(String, String)>,
+}
+
+/// The destination file of a copy.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CopiedObject {
+    /// ETag of the destination file, without the surrounding double quotes.
+    pub e_tag: Option<String>,
+    /// Size of the destination file in bytes.
+    pub size: u64,
+}
+
+/// File names collected by a listing with an upper limit.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LimitedList {
+    /// File names (keys) in ascending order.
+    pub file_names: Vec<String>,
+    /// Whether more files remain after the limit was reached.
+    pub has_more: bool,
+}
+
+/// A single page of a listing, with the pagination metadata of the response.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListResult {
+    /// File names (keys) in ascending order.
+    pub keys: Vec<String>,
+    /// Whether more files remain after this page.
+    pub is_truncated: bool,
+    /// Token to pass to get the next page. `None` on the last page.
+    pub next_token: Option<String>,
+    /// Number of keys in this page.
+    pub key_count: usize,
+}
+
+/// Number and total size of the files under a prefix.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrefixStats {
+    /// Number of files.
+    pub object_count: u64,
+    /// Total size of the files in bytes.
+    pub total_size: u64,
+}
+
+/// A file listed by `ListObjectsV2`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObjectInfo {
+    /// File name (key).
+    pub key: String,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// ETag of the file, without the surrounding double quotes (e.g. the hex MD5 for a single part upload).
+    pub e_tag: Option<String>,
+    /// Time when the file was last modified.
+    pub last_modified: Option<SystemTime>,
+    /// Display name (or ID if there is no display name) of the owner, if the listing fetched it.
+    pub owner: Option<String>,
+    /// Restore status of an archived file, if the backend provides it.
+    pub restore_status: Option<RestoreStatus>,
+}
+
+/// Restore status of an archived file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreStatus {
+    /// Whether the file is being restored.
+    pub in_progress: bool,
+    /// Time when the restored copy expires.
+    pub expiry_date: Option<SystemTime>,
+}
+
+/// Sub-folders and files directly under a prefix, like a directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ListLevel {
+    /// Common prefixes ending with `/` (e.g. `photos/2024/`).
+    pub folders: Vec<String>,
+    /// Files directly under the prefix.
+    pub files: Vec<ObjectInfo>,
+}
+
+/// Access control list (ACL) of a file, returned by `GetObjectAcl`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObjectAcl {
+    /// ID of the owner of the file.
+    pub owner: Option<String>,
+    /// Grants of the file.
+    pub grants: Vec<AclGrant>,
+}
+
+/// A single grant of an [ObjectAcl].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AclGrant {
+    /// ID, email address or group URI of the grantee.
+    pub grantee: Option<String>,
+    /// Permission of the grantee (e.g. `READ`, `FULL_CONTROL`).
+    pub permission: Option<String>,
+}
+
+/// Observed throughput of a single upload or download.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TransferStats {
+    /// Number of transferred bytes.
+    pub bytes: u64,
+    /// Elapsed time of the transfer.
+    pub duration: Duration,
+    /// Transferred bytes per second.
+    pub throughput_bytes_per_sec: f64,
+}
+
+impl TransferStats {
+    pub fn new(bytes: u64, duration: Duration) -> Self {
+        //! Create a new [TransferStats] instance and compute the throughput.
+        //!
+        //! The throughput is 0.0 when the duration is zero.
+        let seconds = duration.as_secs_f64();
+        let throughput_bytes_per_sec = if seconds > 0.0 {
+            bytes as f64 / seconds
+        } else {
+            0.0
+        };
+        Self {
+            bytes,
+            duration,
+            throughput_bytes_per_sec,
+        }
+    }
+}
+
+/// A CORS rule of a bucket.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorsRule {
+    /// Origins allowed to access the bucket (e.g. `https://example.com` or `*`).
+    pub allowed_origins: Vec<String>,
+    /// HTTP methods allowed for the origins (e.g. `GET`, `PUT`).
+    pub allowed_methods: Vec<String>,
+    /// Request headers allowed in a preflight request.
+    pub allowed_headers: Vec<String>,
+    /// Response headers exposed to the browser.
+    pub expose_headers: Vec<String>,
+    /// Time in seconds that the browser may cache the preflight response.
+    pub max_age_seconds: Option<i32>,
+}
+
+/// A lifecycle rule of a bucket.
+///
+/// # Example
+///
+/// ```
+/// use cf_r2_sdk::object::LifecycleRule;
+///
+/// // delete `tmp/` files after 7 days
+/// let rule = LifecycleRule::new("expire-tmp")
+///     .set_prefix("tmp/")
+///     .set_expiration_days(7);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LifecycleRule {
+    /// ID of the rule.
+    pub id: String,
+    /// Prefix of the files the rule applies to. An empty prefix applies to the whole bucket.
+    pub prefix: String,
+    /// Whether the rule is enabled.
+    pub enabled: bool,
+    /// Number of days after which the files are deleted.
+    pub expiration_days: Option<i32>,
+    /// Number of days after which incomplete multipart uploads are aborted.
+    pub abort_incomplete_multipart_upload_days: Option<i32>,
+}
+
+impl LifecycleRule {
+    pub fn new(id: &str) -> Self {
+        //! Create a new enabled [LifecycleRule] applying to the whole bucket without any action.
+        Self {
+            id: id.to_string(),
+            enabled: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn set_prefix(mut self, prefix: &str) -> Self {
+        //! Apply the rule only to the files whose names start with `prefix`.
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    pub fn set_enabled(mut self, enabled: bool) -> Self {
+        //! Enable or disable the rule.
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn set_expiration_days(mut self, days: i32) -> Self {
+        //! Delete the files `days` days after they were uploaded.
+        self.expiration_days = Some(days);
+        self
+    }
+
+    pub fn set_abort_incomplete_multipart_upload_days(mut self, days: i32) -> Self {
+        //! Abort the multipart uploads that are not completed `days` days after they were initiated.
+        self.abort_incomplete_multipart_upload_days = Some(days);
+        self
+    }
+}
+
+/// An in-progress multipart upload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartUpload {
+    /// File name (key) of the upload.
+    pub key: String,
+    /// ID of the upload.
+    pub upload_id: String,
+    /// Time when the upload was initiated.
+    pub initiated: Option<SystemTime>,
+}
@@ -1,8 +1,68 @@
-use aws_sdk_s3::primitives::ByteStream;
-use tokio::{fs::File, io::AsyncReadExt};
+#[cfg(feature = "head-cache")]
+use crate::head_cache::HeadCache;
+use crate::{
+    multipart::{
+        part_size, MAX_SINGLE_PUT_SIZE, MULTIPART_PART_SIZE, STREAMING_CONCURRENCY,
+        STREAM_PART_SIZE,
+    },
+    object::{
+        AclGrant, CopiedObject, DeleteObjectsResult, DownloadResult, DownloadedObject, LimitedList,
+        ListLevel, ListResult, ObjectAcl, ObjectInfo, ObjectMetadata, PrefixStats, RestoreStatus,
+        TransferStats,
+    },
+    options::{ResponseOverrides, UploadOptions},
+    throttle::RateLimiter,
+};
+use aws_sdk_s3::{
+    config::http::{HttpRequest, HttpResponse},
+    error::SdkError,
+    operation::{
+        delete_object::DeleteObjectError,
+        get_object::{GetObjectError, GetObjectOutput},
+        head_object::HeadObjectOutput,
+        list_objects_v2::{builders::ListObjectsV2FluentBuilder, ListObjectsV2Output},
+    },
+    primitives::{ByteStream, ByteStreamError, DateTime, DateTimeFormat},
+    types::{
+        Delete, EncodingType, Object, ObjectCannedAcl, ObjectIdentifier, OptionalObjectAttributes,
+        RequestPayer, RestoreRequest,
+    },
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use md5::{Digest, Md5};
+#[cfg(not(target_arch = "wasm32"))]
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::atomic::{AtomicU64, Ordering},
+    task::{Context, Poll},
+};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::io::{AsyncRead, AsyncReadExt};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::{
+    fs::File,
+    io::{AsyncWriteExt, ReadBuf},
+};
+#[cfg(feature = "cancellation")]
+use tokio_util::sync::CancellationToken;
 
 /// Operator for uploading, downloading, and deleting files to a R2 bucket.
 ///
+/// [Operator] is `Send + Sync` and cheap to clone,
+/// so it can be shared across tokio tasks (e.g. in an `Arc`).
+///
+/// The clones, and the operators created by [Operator::with_bucket] and [Operator::with_timeout],
+/// share the same client and its HTTP connection pool.
+/// Each call of [Builder::create_client_result](crate::builder::Builder::create_client_result)
+/// creates a new client with its own pool, so create the [Operator] once and clone it.
+///
 /// # Example
 ///
 /// ```
@@ -44,28 +104,227 @@ use tokio::{fs::File, io::AsyncReadExt};
 /// ```
 #[derive(Debug, Clone)]
 pub struct Operator {
-    bucket_name: String,
-    client: aws_sdk_s3::Client,
+    pub(crate) bucket_name: String,
+    pub(crate) client: aws_sdk_s3::Client,
+    pub(crate) max_attempts: u32,
+    pub(crate) rate_limiter: Option<Arc<RateLimiter>>,
+    pub(crate) verify_integrity: bool,
+    pub(crate) request_payer: bool,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) endpoint: Option<String>,
+    pub(crate) public_base_url: Option<String>,
+    #[cfg(feature = "head-cache")]
+    pub(crate) head_cache: Option<Arc<HeadCache>>,
 }
 
 impl Operator {
     pub fn new(bucket_name: String, client: aws_sdk_s3::Client) -> Self {
         //! Create a new [Operator] instance.
+        //!
+        //! default value of max attempts is 3.
         Self {
             bucket_name,
             client,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            rate_limiter: None,
+            verify_integrity: false,
+            request_payer: false,
+            timeout: None,
+            endpoint: None,
+            public_base_url: None,
+            #[cfg(feature = "head-cache")]
+            head_cache: None,
+        }
+    }
+
+    pub(crate) fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub(crate) fn with_rate_limit(mut self, bytes_per_sec: Option<u64>) -> Self {
+        self.rate_limiter =
+            bytes_per_sec.map(|bytes_per_sec| Arc::new(RateLimiter::new(bytes_per_sec)));
+        self
+    }
+
+    pub(crate) fn with_verify_integrity(mut self, verify_integrity: bool) -> Self {
+        self.verify_integrity = verify_integrity;
+        self
+    }
+
+    pub(crate) fn with_request_payer(mut self, request_payer: bool) -> Self {
+        self.request_payer = request_payer;
+        self
+    }
+
+    /// `x-amz-request-payer` of the object requests, set when the requester pays.
+    pub(crate) fn request_payer(&self) -> Option<RequestPayer> {
+        self.request_payer.then_some(RequestPayer::Requester)
+    }
+
+    #[cfg(feature = "head-cache")]
+    pub(crate) fn with_head_cache(mut self, head_cache: Option<(usize, Duration)>) -> Self {
+        self.head_cache =
+            head_cache.map(|(capacity, max_age)| Arc::new(HeadCache::new(capacity, max_age)));
+        self
+    }
+
+    pub(crate) fn with_public_url(
+        mut self,
+        endpoint: Option<String>,
+        public_base_url: Option<String>,
+    ) -> Self {
+        self.endpoint = endpoint;
+        self.public_base_url = public_base_url;
+        self
+    }
+
+    pub fn public_url(&self, file_name: &str) -> Option<String> {
+        //! Get the URL of a file in the R2 bucket.
+        //!
+        //! The URL is the public base URL set by [Builder::set_public_base_url](crate::builder::Builder::set_public_base_url)
+        //! (e.g. a custom domain or `https://pub-<hash>.r2.dev`) followed by the key,
+        //! or `<endpoint>/<bucket>/<key>` if it is not set.
+        //! The key is percent-encoded except for `/` and unreserved characters.
+        //! `None` is returned if the [Operator] was created with [Operator::new].
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //!
+        //! fn main() -> Result<(), Error> {
+        //!     let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!         .set_bucket_name("bucket_name".to_string())
+        //!         .set_access_key_id("access_key_id".to_string())
+        //!         .set_secret_access_key("secret_access_key".to_string())
+        //!         .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //!         .set_public_base_url("https://assets.example.com".to_string())
+        //!         .create_client_result()?;
+        //!
+        //!     assert_eq!(
+        //!         object.public_url("images/a b.png").as_deref(),
+        //!         Some("https://assets.example.com/images/a%20b.png")
+        //!     );
+        //!     Ok(())
+        //! }
+        //! ```
+        let key = encode_key(file_name);
+        match (&self.public_base_url, &self.endpoint) {
+            (Some(public_base_url), _) => {
+                Some(format!("{}/{}", public_base_url.trim_end_matches('/'), key))
+            }
+            (None, Some(endpoint)) => Some(format!(
+                "{}/{}/{}",
+                endpoint.trim_end_matches('/'),
+                self.bucket_name,
+                key
+            )),
+            (None, None) => None,
         }
     }
 
+    /// Wait until `bytes` may be transferred under the rate limit, if any.
+    pub(crate) async fn throttle(&self, bytes: u64) {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire(bytes).await;
+        }
+    }
+
+    /// Wrap the upload `body` so that its chunks are sent under the rate limit, if any.
+    pub(crate) fn throttled_body(&self, body: ByteStream) -> ByteStream {
+        match &self.rate_limiter {
+            Some(rate_limiter) => ByteStream::new(crate::throttle::ThrottledBody::wrap(
+                body.into_inner(),
+                rate_limiter.clone(),
+            )),
+            None => body,
+        }
+    }
+
+    /// Read the whole `body`, throttling each chunk under the rate limit, if any.
+    pub(crate) async fn read_body(&self, mut body: ByteStream) -> Result<Vec<u8>, ByteStreamError> {
+        if self.rate_limiter.is_none() {
+            return Ok(body.collect().await?.into_bytes().to_vec());
+        }
+        let mut bytes = Vec::new();
+        while let Some(chunk) = body.try_next().await? {
+            self.throttle(chunk.len() as u64).await;
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+
+    pub fn with_bucket(&self, bucket_name: &str) -> Self {
+        //! Create a new [Operator] instance for another bucket sharing the same client.
+        //!
+        //! For a single operation, the `*_to`, `*_from`, and `*_in` methods can be used instead.
+        Self {
+            bucket_name: bucket_name.to_string(),
+            ..self.clone()
+        }
+    }
+
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        //! Create a new [Operator] instance sharing the same client whose operations
+        //! fail with [OperationError::Timeout](crate::error::OperationError::Timeout)
+        //! when they do not complete within `timeout`.
+        //!
+        //! The deadline covers the whole method call, including all the requests, retries and
+        //! reading the body, independently of the timeouts of aws-sdk-s3
+        //! ([Builder::set_operation_timeout](crate::builder::Builder::set_operation_timeout)).
+        //! For [Operator::download_stream] and [Operator::get_range_stream], which return before
+        //! the body is read, the deadline also applies to reading the returned stream or reader,
+        //! so a stalled body fails instead of waiting forever.
+        //! The timed out request is dropped, so a timed out upload or deletion may or may not have been applied.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::{Error, OperationError};
+        //! # use std::time::Duration;
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .create_client_result()?;
+        //! match object
+        //!     .with_timeout(Duration::from_secs(5))
+        //!     .download("index.html")
+        //!     .await
+        //! {
+        //!     Ok(bytes) => println!("{} bytes", bytes.len()),
+        //!     Err(OperationError::Timeout(timeout)) => println!("timed out after {:?}", timeout),
+        //!     Err(err) => return Err(err.into()),
+        //! }
+        //! # Ok(())
+        //! # }
+        //! ```
+        Self {
+            timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
     pub async fn upload_file(
         &self,
         file_name: &str,
         mime_type: &str,
-        file_path: &str,
+        file_path: impl AsRef<Path>,
         cache_control: Option<&str>,
     ) -> Result<(), crate::error::OperationError> {
         //! Upload a file to the R2 bucket.
         //!
+        //! `file_path` can be any path type (e.g. `&str`, `&Path` or `PathBuf`),
+        //! so paths that are not valid UTF-8 are also supported.
+        //!
         //! # Example
         //!
         //! ```
@@ -106,40 +365,29 @@ impl Operator {
         //!   Ok(())
         //! }
         //! ```
-        let mut file = File::open(file_path).await?;
-
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer).await?;
-
-        match &self
-            .client
-            .put_object()
-            .bucket(&self.bucket_name)
-            .key(file_name)
-            .content_type(mime_type)
-            .cache_control(cache_control.unwrap_or("no-cache"))
-            .body(ByteStream::from(buffer))
-            .send()
+        self.instrument("upload_file", async {
+            self.upload_file_to(
+                &self.bucket_name,
+                file_name,
+                mime_type,
+                file_path,
+                cache_control,
+            )
             .await
-        {
-            Ok(_) => (),
-            Err(err) => {
-                return Err(crate::error::OperationError::AWSSdkS3PutObjectError(
-                    err.to_string(),
-                ))
-            }
-        };
-        Ok(())
+        })
+        .await
     }
 
-    pub async fn upload_binary(
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload_file_to(
         &self,
+        bucket_name: &str,
         file_name: &str,
         mime_type: &str,
-        binary: &[u8],
+        file_path: impl AsRef<Path>,
         cache_control: Option<&str>,
     ) -> Result<(), crate::error::OperationError> {
-        //! Upload binary data to the R2 bucket.
+        //! Upload a file to the given R2 bucket instead of the configured one.
         //!
         //! # Example
         //!
@@ -171,45 +419,74 @@ impl Operator {
         //!        .set_region(region)
         //!        .create_client_result()?;
         //!
-        //!    // upload binary data
+        //!    let other_bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!
+        //!    // upload file
         //!    object
-        //!        .upload_binary("doctest_operator_upload_binary.txt", "test/plain", b"Hello, World!", None)
+        //!        .upload_file_to(&other_bucket_name, "doctest_operator_upload_file_to.jpg", "image/jpeg", "./data/sample.jpg", None)
         //!        .await?;
         //!
         //!    // clean up
-        //!    object.delete("doctest_operator_upload_binary.txt").await?;
+        //!    object.delete_from(&other_bucket_name, "doctest_operator_upload_file_to.jpg").await?;
         //!    Ok(())
         //! }
         //! ```
-        match &self
-            .client
-            .put_object()
-            .bucket(&self.bucket_name)
-            .key(file_name)
-            .content_type(mime_type)
-            .cache_control(cache_control.unwrap_or("no-cache"))
-            .body(ByteStream::from(binary.to_vec()))
-            .send()
-            .await
-        {
-            Ok(_) => (),
-            Err(err) => {
-                return Err(crate::error::OperationError::AWSSdkS3PutObjectError(
-                    err.to_string(),
-                ))
-            }
-        };
-        Ok(())
+        self.instrument("upload_file_to", async {
+            check_content_type(mime_type)?;
+            let mut file = File::open(file_path).await?;
+
+            let mut buffer = Vec::new();
+            file.read_to_end(&mut buffer).await?;
+            let expected = self
+                .verify_integrity
+                .then(|| md5_etag(&md5_digest(&buffer)));
+
+            match &self
+                .client
+                .put_object()
+                .set_request_payer(self.request_payer())
+                .bucket(bucket_name)
+                .key(file_name)
+                .content_type(mime_type)
+                .cache_control(cache_control.unwrap_or("no-cache"))
+                .content_length(buffer.len() as i64)
+                .body(self.throttled_body(ByteStream::from(buffer)))
+                .send()
+                .await
+            {
+                Ok(output) => check_etag(expected, output.e_tag())?,
+                Err(err) => {
+                    return Err(crate::error::OperationError::AWSSdkS3PutObjectError(
+                        err.into(),
+                    ))
+                }
+            };
+            Ok(())
+        })
+        .await
     }
 
-    pub async fn download(&self, file_name: &str) -> Result<Vec<u8>, crate::error::OperationError> {
-        //! Download a file as binary data from the R2 bucket.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload_file_streaming(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        file_path: impl AsRef<Path>,
+        options: &UploadOptions,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Upload a file to the R2 bucket by streaming it from the disk.
+        //!
+        //! Unlike [Operator::upload_file], the file is not read into memory,
+        //! and the `Content-Length` header is set from the file size.
+        //! With the object lock settings of [UploadOptions], an upload rejected because the backend
+        //! does not support object lock returns [OperationError::NotSupported](crate::error::OperationError::NotSupported).
         //!
         //! # Example
         //!
         //! ```
         //! use cf_r2_sdk::builder::Builder;
         //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::options::UploadOptions;
         //! use dotenvy::dotenv;
         //! use std::env;
         //!
@@ -235,51 +512,214 @@ impl Operator {
         //!        .set_region(region)
         //!        .create_client_result()?;
         //!
+        //!    // upload file
         //!    object
-        //!        .upload_binary("doctest_operator_download.txt", "test/plain", b"Hello, World!", None)
+        //!        .upload_file_streaming("doctest_operator_upload_file_streaming.jpg", "image/jpeg", "./data/sample.jpg", &UploadOptions::new())
         //!        .await?;
         //!
-        //!    // download binary data
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_file_streaming.jpg").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_file_streaming", async {
+            let file_path = file_path.as_ref();
+            check_content_type(mime_type)?;
+            let content_length = tokio::fs::metadata(file_path).await?.len();
+            let expected = match self.verify_integrity {
+                true => Some(md5_etag(&file_md5_digest(file_path).await?)),
+                false => None,
+            };
+            let body = ByteStream::from_path(file_path).await?;
+
+            let request = self
+                .client
+                .put_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .content_type(mime_type)
+                .content_length(content_length as i64)
+                .body(self.throttled_body(body));
+            match options.apply(request).send().await {
+                Ok(output) => check_etag(expected, output.e_tag())?,
+                Err(err) if options.has_object_lock() => {
+                    return match object_lock_not_supported(err.into()) {
+                        Ok(message) => Err(crate::error::OperationError::NotSupported(message)),
+                        Err(err) => Err(crate::error::OperationError::AWSSdkS3PutObjectError(err)),
+                    }
+                }
+                Err(err) => {
+                    return Err(crate::error::OperationError::AWSSdkS3PutObjectError(
+                        err.into(),
+                    ))
+                }
+            };
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn upload_reader<R>(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        mut reader: R,
+        cache_control: Option<&str>,
+    ) -> Result<(), crate::error::OperationError>
+    where
+        R: AsyncRead + Unpin + Send,
+    {
+        //! Upload the data read from `reader` until EOF to the R2 bucket.
+        //!
+        //! The length does not have to be known in advance.
+        //! Data smaller than 64 MiB is uploaded with a single `PutObject`,
+        //! and larger data with a multipart upload in parts of 64 MiB,
+        //! keeping at most a few parts in memory.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // upload from any AsyncRead
+        //!    let reader: &[u8] = b"Hello, World!";
         //!    object
-        //!        .download("doctest_operator_download.txt")
+        //!        .upload_reader("doctest_operator_upload_reader.txt", "text/plain", reader, None)
         //!        .await?;
         //!
         //!    // clean up
-        //!    object.delete("doctest_operator_download.txt").await?;
-        //!   Ok(())
+        //!    object.delete("doctest_operator_upload_reader.txt").await?;
+        //!    Ok(())
         //! }
         //! ```
-        let object = match self
-            .client
-            .clone()
-            .get_object()
-            .bucket(&self.bucket_name)
-            .key(file_name)
-            .send()
-            .await
-        {
-            Ok(object) => object,
-            Err(err) => {
-                return Err(crate::error::OperationError::AWSSdkS3GetObjectError(
-                    err.to_string(),
-                ))
+        self.instrument("upload_reader", async {
+            check_content_type(mime_type)?;
+            let first = read_part(&mut reader).await?;
+            if first.len() < MULTIPART_PART_SIZE {
+                return self
+                    .upload_binary_to(
+                        &self.bucket_name,
+                        file_name,
+                        mime_type,
+                        &first,
+                        cache_control,
+                    )
+                    .await;
             }
-        };
-        let result = match object.body.collect().await {
-            Ok(result) => result.into_bytes().to_vec(),
-            Err(err) => return Err(crate::error::OperationError::AWSSdkS3ByteStreamError(err)),
-        };
-        Ok(result)
+            let rest = futures::stream::unfold(Some(reader), |reader| async move {
+                let mut reader = reader?;
+                match read_part(&mut reader).await {
+                    Ok(part) if part.is_empty() => None,
+                    Ok(part) => Some((Ok(part), Some(reader))),
+                    // end the stream after the error
+                    Err(err) => Some((Err(err.into()), None)),
+                }
+            });
+            let parts = futures::stream::once(async { Ok(first) }).chain(rest);
+            self.upload_multipart(
+                &self.bucket_name,
+                file_name,
+                mime_type,
+                cache_control,
+                parts,
+                STREAMING_CONCURRENCY,
+            )
+            .await?;
+            Ok(())
+        })
+        .await
     }
 
-    pub async fn delete(&self, file_name: &str) -> Result<(), crate::error::OperationError> {
-        //! Delete a file from the R2 bucket.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload_stdin(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        cache_control: Option<&str>,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Upload the data read from stdin until EOF to the R2 bucket.
+        //!
+        //! This works the same as [Operator::upload_reader] with stdin,
+        //! e.g. for `tar cz ./data | my-uploader backup.tar.gz`.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .create_client_result()?;
+        //! // upload the piped data
+        //! object
+        //!     .upload_stdin("backup.tar.gz", "application/gzip", None)
+        //!     .await?;
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("upload_stdin", async {
+            self.upload_reader(file_name, mime_type, tokio::io::stdin(), cache_control)
+                .await
+        })
+        .await
+    }
+
+    pub async fn upload_from_stream<S, E>(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        stream: S,
+    ) -> Result<(), crate::error::OperationError>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send,
+        E: std::fmt::Display,
+    {
+        //! Upload the chunks of `stream` to the R2 bucket without buffering the whole data,
+        //! e.g. to proxy the body of an incoming HTTP request.
+        //!
+        //! The chunks are accumulated into parts of 8 MiB, and each part is uploaded with a
+        //! multipart upload as soon as it is filled.
+        //! Data smaller than 8 MiB is uploaded with a single `PutObject`.
+        //! An error of the stream aborts the upload and is returned as
+        //! [OperationError::Other](crate::error::OperationError::Other).
         //!
         //! # Example
         //!
         //! ```
         //! use cf_r2_sdk::builder::Builder;
         //! use cf_r2_sdk::error::Error;
+        //! use bytes::Bytes;
         //! use dotenvy::dotenv;
         //! use std::env;
         //!
@@ -305,36 +745,64 @@ impl Operator {
         //!        .set_region(region)
         //!        .create_client_result()?;
         //!
+        //!    let chunks = vec![
+        //!        Ok::<_, std::io::Error>(Bytes::from_static(b"Hello, ")),
+        //!        Ok(Bytes::from_static(b"World!")),
+        //!    ];
         //!    object
-        //!        .upload_binary("doctest_operator_delete.txt", "test/plain", b"Hello, World!", None)
+        //!        .upload_from_stream("doctest_operator_upload_from_stream.txt", "text/plain", futures::stream::iter(chunks))
         //!        .await?;
         //!
-        //!    // delete file
-        //!    object.delete("doctest_operator_delete.txt").await?;
+        //!    let bin: Vec<u8> = object.download("doctest_operator_upload_from_stream.txt").await?;
+        //!    assert_eq!(bin, b"Hello, World!");
         //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_from_stream.txt").await?;
         //!    Ok(())
         //! }
         //! ```
-        match &self
-            .client
-            .delete_object()
-            .bucket(&self.bucket_name)
-            .key(file_name)
-            .send()
-            .await
-        {
-            Ok(_) => (),
-            Err(err) => {
-                return Err(crate::error::OperationError::AWSSdkS3DeleteObjectError(
-                    err.to_string(),
-                ))
+        self.instrument("upload_from_stream", async {
+            check_content_type(mime_type)?;
+            let mut parts = Box::pin(chunk_parts(stream, STREAM_PART_SIZE));
+            let first = match parts.next().await {
+                Some(part) => part?,
+                None => Vec::new(),
+            };
+            if first.len() < STREAM_PART_SIZE {
+                return self
+                    .upload_binary_to(&self.bucket_name, file_name, mime_type, &first, None)
+                    .await;
             }
-        }
-        Ok(())
+            let parts = futures::stream::once(async { Ok(first) }).chain(parts);
+            self.upload_multipart(
+                &self.bucket_name,
+                file_name,
+                mime_type,
+                None,
+                parts,
+                STREAMING_CONCURRENCY,
+            )
+            .await?;
+            Ok(())
+        })
+        .await
     }
 
-    pub async fn list_objects(&self) -> Result<Vec<String>, crate::error::OperationError> {
-        //! Get file names vector from the R2 bucket.
+    pub async fn upload_binary(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        binary: &[u8],
+        cache_control: Option<&str>,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Upload binary data to the R2 bucket.
+        //!
+        //! Binary data larger than 5 GiB, the limit of a single `PutObject`,
+        //! is uploaded with a multipart upload in parts of at least 64 MiB.
+        //! The `Content-Length` of the request is set from `binary.len()`.
+        //! An invalid `mime_type` (e.g. an empty string) returns
+        //! [OperationError::InvalidContentType](crate::error::OperationError::InvalidContentType);
+        //! use [OCTET_STREAM] when the type is unknown.
         //!
         //! # Example
         //!
@@ -366,44 +834,5386 @@ impl Operator {
         //!        .set_region(region)
         //!        .create_client_result()?;
         //!
+        //!    // upload binary data
         //!    object
-        //!       .upload_binary("doctest_operator_list_objects.txt", "test/plain", b"Hello, World!", None)
-        //!       .await?;
-        //!
-        //!    // get file names vector
-        //!    let file_names: Vec<String> = object.list_objects().await?;
-        //!
-        //!    for file_name in file_names {
-        //!       println!("{}", file_name);
-        //!    }
+        //!        .upload_binary("doctest_operator_upload_binary.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
         //!
         //!    // clean up
-        //!    object.delete("doctest_operator_list_objects.txt").await?;
+        //!    object.delete("doctest_operator_upload_binary.txt").await?;
         //!    Ok(())
         //! }
         //! ```
-        let response = &mut self
-            .client
-            .list_objects_v2()
-            .bucket(&self.bucket_name)
-            .max_keys(10)
-            .into_paginator()
-            .send();
-        let mut objects = Vec::new();
-        while let Some(result) = response.next().await {
-            match result {
-                Ok(output) => {
-                    for object in output.contents() {
-                        objects.push(object.key().unwrap_or("Unknown").to_owned());
-                    }
-                }
-                Err(err) => {
-                    return Err(crate::error::OperationError::AWSSdkS3ListObjectsV2Error(
-                        err.to_string(),
-                    ))
-                }
-            }
-        }
-        Ok(objects)
+        self.instrument("upload_binary", async {
+            self.upload_binary_to(
+                &self.bucket_name,
+                file_name,
+                mime_type,
+                binary,
+                cache_control,
+            )
+            .await
+        })
+        .await
+    }
+
+    pub async fn upload_binary_with_options(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        binary: &[u8],
+        options: &UploadOptions,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Upload binary data to the R2 bucket with [UploadOptions],
+        //! e.g. to set `Expires` together with `Cache-Control`.
+        //!
+        //! The data is uploaded with a single `PutObject`, so it must not be larger than 5 GiB.
+        //! With the object lock settings of [UploadOptions], an upload rejected because the backend
+        //! does not support object lock returns [OperationError::NotSupported](crate::error::OperationError::NotSupported).
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::options::UploadOptions;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //! use std::time::{Duration, SystemTime};
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!
+        //!    // cache for a year, also for the caches which only understand Expires
+        //!    let options = UploadOptions::new()
+        //!        .set_cache_control("public, max-age=31536000")
+        //!        .set_expires(SystemTime::now() + Duration::from_secs(31536000));
+        //!    object
+        //!        .upload_binary_with_options("doctest_operator_upload_binary_with_options.txt", "text/plain", b"Hello, World!", &options)
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_binary_with_options.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_binary_with_options", async {
+            check_content_type(mime_type)?;
+            let expected = self.verify_integrity.then(|| md5_etag(&md5_digest(binary)));
+
+            let request = self
+                .client
+                .put_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .content_type(mime_type)
+                .content_length(binary.len() as i64)
+                .body(self.throttled_body(ByteStream::from(binary.to_vec())));
+            match options.apply(request).send().await {
+                Ok(output) => check_etag(expected, output.e_tag())?,
+                Err(err) if options.has_object_lock() => {
+                    return match object_lock_not_supported(err.into()) {
+                        Ok(message) => Err(crate::error::OperationError::NotSupported(message)),
+                        Err(err) => Err(crate::error::OperationError::AWSSdkS3PutObjectError(err)),
+                    }
+                }
+                Err(err) => {
+                    return Err(crate::error::OperationError::AWSSdkS3PutObjectError(
+                        err.into(),
+                    ))
+                }
+            };
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn upload_binary_with_info(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        binary: &[u8],
+        cache_control: Option<&str>,
+    ) -> Result<ObjectInfo, crate::error::OperationError> {
+        //! Upload binary data to the R2 bucket, and return the [ObjectInfo] of the uploaded file
+        //! without a `HeadObject` request.
+        //!
+        //! The size is the length of `binary` and the ETag is taken from the response.
+        //! `last_modified` is `None`, since the upload response does not include it.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    let info = object
+        //!        .upload_binary_with_info("doctest_operator_upload_binary_with_info.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!    assert_eq!(info.size, 13);
+        //!    println!("ETag: {:?}", info.e_tag);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_binary_with_info.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_binary_with_info", async {
+            let e_tag = self
+                .put_binary(
+                    &self.bucket_name,
+                    file_name,
+                    mime_type,
+                    binary,
+                    cache_control,
+                )
+                .await?;
+            Ok(ObjectInfo {
+                key: file_name.to_string(),
+                size: binary.len() as u64,
+                e_tag,
+                ..ObjectInfo::default()
+            })
+        })
+        .await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload_file_with_info(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        file_path: impl AsRef<Path>,
+        cache_control: Option<&str>,
+    ) -> Result<ObjectInfo, crate::error::OperationError> {
+        //! Upload a file to the R2 bucket, and return the [ObjectInfo] of the uploaded file
+        //! without a `HeadObject` request.
+        //!
+        //! The file is read into memory. See [Operator::upload_binary_with_info] for the returned fields.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    let info = object
+        //!        .upload_file_with_info("doctest_operator_upload_file_with_info.md", "text/markdown", "./data/LICENSE.md", None)
+        //!        .await?;
+        //!    println!("{}: {} bytes, ETag {:?}", info.key, info.size, info.e_tag);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_file_with_info.md").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_file_with_info", async {
+            let binary = tokio::fs::read(file_path).await?;
+            self.upload_binary_with_info(file_name, mime_type, &binary, cache_control)
+                .await
+        })
+        .await
+    }
+
+    pub async fn upload_and_url(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        binary: &[u8],
+        cache_control: Option<&str>,
+    ) -> Result<String, crate::error::OperationError> {
+        //! Upload binary data to the R2 bucket and return the URL of the file.
+        //!
+        //! The URL is built by [Operator::public_url].
+        //! [OperationError::PublicUrlNotSet](crate::error::OperationError::PublicUrlNotSet)
+        //! is returned before uploading if no URL can be built.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // upload and get the URL
+        //!    let url = object
+        //!        .upload_and_url("doctest_operator_upload_and_url.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!    println!("{}", url);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_and_url.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_and_url", async {
+            let url = self
+                .public_url(file_name)
+                .ok_or(crate::error::OperationError::PublicUrlNotSet)?;
+            self.upload_binary(file_name, mime_type, binary, cache_control)
+                .await?;
+            Ok(url)
+        })
+        .await
+    }
+
+    #[cfg(feature = "infer")]
+    pub async fn upload_binary_with_detected_type(
+        &self,
+        file_name: &str,
+        binary: &[u8],
+        cache_control: Option<&str>,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Upload binary data to the R2 bucket, detecting the mime type from the leading bytes.
+        //!
+        //! The mime type is detected by the [infer](https://crates.io/crates/infer) crate
+        //! and falls back to [OCTET_STREAM] when it is unknown.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // upload binary data as "image/png"
+        //!    let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR";
+        //!    object
+        //!        .upload_binary_with_detected_type("doctest_operator_detected_type.png", png, None)
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_detected_type.png").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_binary_with_detected_type", async {
+            self.upload_binary_to(
+                &self.bucket_name,
+                file_name,
+                detect_content_type(binary),
+                binary,
+                cache_control,
+            )
+            .await
+        })
+        .await
+    }
+
+    pub async fn upload_binary_idempotent(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        binary: &[u8],
+        cache_control: Option<&str>,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Upload binary data to the R2 bucket only once, so that retrying it is safe.
+        //!
+        //! The upload is conditional on the absence of the file (`If-None-Match: *`).
+        //! If the file already exists with the same content, e.g. because a previous attempt
+        //! succeeded but its response was lost, this returns `Ok`.
+        //! The content is compared by recomputing the ETag of `binary`, also for a multipart ETag
+        //! (`"<md5>-<parts>"`) with the part sizes this crate and common tools use.
+        //! If it exists with different content, this returns
+        //! [OperationError::PreconditionFailed](crate::error::OperationError::PreconditionFailed)
+        //! and the existing file is kept.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // retrying is safe
+        //!    for _ in 0..2 {
+        //!        object
+        //!            .upload_binary_idempotent("doctest_operator_upload_binary_idempotent.txt", "text/plain", b"Hello, World!", None)
+        //!            .await?;
+        //!    }
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_binary_idempotent.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_binary_idempotent", async {
+            check_content_type(mime_type)?;
+            let expected = md5_etag(&md5_digest(binary));
+            match self
+                .client
+                .put_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .content_type(mime_type)
+                .cache_control(cache_control.unwrap_or("no-cache"))
+                .if_none_match("*")
+                .content_length(binary.len() as i64)
+                .body(self.throttled_body(ByteStream::from(binary.to_vec())))
+                .send()
+                .await
+            {
+                Ok(output) => check_etag(self.verify_integrity.then_some(expected), output.e_tag()),
+                // 409 is returned by R2 when a conflicting conditional write is in progress
+                Err(err) if matches!(status_code(&err), Some(409 | 412)) => {
+                    let existing = self.head(file_name).await?;
+                    let mut matcher =
+                        EtagMatcher::new(binary.len() as u64, existing.e_tag().unwrap_or_default());
+                    matcher.update(binary);
+                    match matcher.matches() {
+                        true => Ok(()),
+                        false => Err(crate::error::OperationError::PreconditionFailed(format!(
+                            "{} already exists with different content.",
+                            file_name
+                        ))),
+                    }
+                }
+                Err(err) => Err(crate::error::OperationError::AWSSdkS3PutObjectError(
+                    err.into(),
+                )),
+            }
+        })
+        .await
+    }
+
+    pub async fn upload_binary_to(
+        &self,
+        bucket_name: &str,
+        file_name: &str,
+        mime_type: &str,
+        binary: &[u8],
+        cache_control: Option<&str>,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Upload binary data to the given R2 bucket instead of the configured one.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    let other_bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!
+        //!    // upload binary data
+        //!    object
+        //!        .upload_binary_to(&other_bucket_name, "doctest_operator_upload_binary_to.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete_from(&other_bucket_name, "doctest_operator_upload_binary_to.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_binary_to", async {
+            self.put_binary(bucket_name, file_name, mime_type, binary, cache_control)
+                .await?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Upload `binary` with a single `PutObject`, or a multipart upload if it is too large,
+    /// and return the ETag of the file.
+    pub(crate) async fn put_binary(
+        &self,
+        bucket_name: &str,
+        file_name: &str,
+        mime_type: &str,
+        binary: &[u8],
+        cache_control: Option<&str>,
+    ) -> Result<Option<String>, crate::error::OperationError> {
+        check_content_type(mime_type)?;
+        if binary.len() as u64 > MAX_SINGLE_PUT_SIZE {
+            return self
+                .upload_binary_multipart(bucket_name, file_name, mime_type, binary, cache_control)
+                .await;
+        }
+        let expected = self.verify_integrity.then(|| md5_etag(&md5_digest(binary)));
+        match self
+            .client
+            .put_object()
+            .set_request_payer(self.request_payer())
+            .bucket(bucket_name)
+            .key(file_name)
+            .content_type(mime_type)
+            .cache_control(cache_control.unwrap_or("no-cache"))
+            .content_length(binary.len() as i64)
+            .body(self.throttled_body(ByteStream::from(binary.to_vec())))
+            .send()
+            .await
+        {
+            Ok(output) => {
+                check_etag(expected, output.e_tag())?;
+                Ok(output.e_tag().map(unquote_etag))
+            }
+            Err(err) => Err(crate::error::OperationError::AWSSdkS3PutObjectError(
+                err.into(),
+            )),
+        }
+    }
+
+    pub async fn download(&self, file_name: &str) -> Result<Vec<u8>, crate::error::OperationError> {
+        //! Download a file as binary data from the R2 bucket.
+        //!
+        //! If reading the body fails midway, the download is retried up to the max attempts set by
+        //! [Builder::set_max_attempts](crate::builder::Builder::set_max_attempts).
+        //!
+        //! A redirect response (e.g. from a proxy in front of the bucket) is not followed and returns
+        //! [OperationError::Redirect](crate::error::OperationError::Redirect) with the redirected location.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // download binary data
+        //!    object
+        //!        .download("doctest_operator_download.txt")
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download.txt").await?;
+        //!   Ok(())
+        //! }
+        //! ```
+        self.instrument("download", async {
+            self.download_from(&self.bucket_name, file_name).await
+        })
+        .await
+    }
+
+    #[cfg(feature = "decompress")]
+    pub async fn download_auto(
+        &self,
+        file_name: &str,
+    ) -> Result<Vec<u8>, crate::error::OperationError> {
+        //! Download a file as binary data from the R2 bucket, decoding its stored `Content-Encoding`.
+        //!
+        //! `gzip`, `deflate` and `br` are decompressed, so the original bytes are returned.
+        //! A file without `Content-Encoding` is returned as it is.
+        //! Other encodings return
+        //! [OperationError::UnsupportedEncoding](crate::error::OperationError::UnsupportedEncoding).
+        //! A corrupt body, or a body decoded to more than 1 GiB, returns
+        //! [OperationError::DecompressError](crate::error::OperationError::DecompressError).
+        //!
+        //! Requires the `decompress` feature.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! #
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .set_region("auto".to_string())
+        //! #     .create_client_result()?;
+        //! // `data.json` was uploaded with `Content-Encoding: gzip`
+        //! let bin: Vec<u8> = object.download_auto("data.json").await?;
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("download_auto", async {
+            let object = match self
+                .client
+                .get_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .send()
+                .await
+            {
+                Ok(object) => object,
+                Err(err) => return Err(get_object_error(err)),
+            };
+            let content_encoding = object.content_encoding.clone().unwrap_or_default();
+            let body = match self.read_body(object.body).await {
+                Ok(body) => body,
+                Err(err) => return Err(crate::error::OperationError::AWSSdkS3ByteStreamError(err)),
+            };
+            crate::decompress::decode(&content_encoding, body)
+        })
+        .await
+    }
+
+    pub async fn download_from(
+        &self,
+        bucket_name: &str,
+        file_name: &str,
+    ) -> Result<Vec<u8>, crate::error::OperationError> {
+        //! Download a file as binary data from the given R2 bucket instead of the configured one.
+        //!
+        //! If reading the body fails midway, the download is retried up to the max attempts set by
+        //! [Builder::set_max_attempts](crate::builder::Builder::set_max_attempts).
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    let other_bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!
+        //!    object
+        //!        .upload_binary_to(&other_bucket_name, "doctest_operator_download_from.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // download binary data
+        //!    object
+        //!        .download_from(&other_bucket_name, "doctest_operator_download_from.txt")
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete_from(&other_bucket_name, "doctest_operator_download_from.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("download_from", async {
+            // The SDK retries the request itself, but not a failure while reading the body,
+            // so the whole GET is re-issued when the body read fails.
+            let mut attempt = 1;
+            loop {
+                let object = match self
+                    .client
+                    .get_object()
+                    .set_request_payer(self.request_payer())
+                    .bucket(bucket_name)
+                    .key(file_name)
+                    .send()
+                    .await
+                {
+                    Ok(object) => object,
+                    Err(err) => return Err(get_object_error(err)),
+                };
+                match self.read_body(object.body).await {
+                    Ok(result) => return Ok(result),
+                    Err(_) if attempt < self.max_attempts => attempt += 1,
+                    Err(err) => {
+                        return Err(crate::error::OperationError::AWSSdkS3ByteStreamError(err))
+                    }
+                }
+            }
+        })
+        .await
+    }
+
+    pub async fn download_capped(
+        &self,
+        file_name: &str,
+        max_bytes: usize,
+    ) -> Result<Vec<u8>, crate::error::OperationError> {
+        //! Download a file as binary data from the R2 bucket, refusing files larger than `max_bytes`.
+        //!
+        //! Return [OperationError::TooLarge](crate::error::OperationError::TooLarge) before reading the body
+        //! if `Content-Length` exceeds `max_bytes`.
+        //! The body is also checked while reading, so no more than `max_bytes` is kept in memory.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download_capped.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // download at most 1 MiB
+        //!    let bin: Vec<u8> = object.download_capped("doctest_operator_download_capped.txt", 1024 * 1024).await?;
+        //!
+        //!    println!("{:?}", bin);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download_capped.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("download_capped", async {
+            let limit = max_bytes as u64;
+            let mut object = match self
+                .client
+                .get_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .send()
+                .await
+            {
+                Ok(object) => object,
+                Err(err) => return Err(get_object_error(err)),
+            };
+            let size = object.content_length().unwrap_or(0) as u64;
+            if size > limit {
+                return Err(crate::error::OperationError::TooLarge { size, limit });
+            }
+            let mut bytes = Vec::with_capacity(size as usize);
+            while let Some(chunk) = object.body.try_next().await? {
+                let size = (bytes.len() + chunk.len()) as u64;
+                if size > limit {
+                    return Err(crate::error::OperationError::TooLarge { size, limit });
+                }
+                self.throttle(chunk.len() as u64).await;
+                bytes.extend_from_slice(&chunk);
+            }
+            Ok(bytes)
+        })
+        .await
+    }
+
+    pub async fn append(
+        &self,
+        file_name: &str,
+        data: &[u8],
+    ) -> Result<(), crate::error::OperationError> {
+        //! Append `data` to the end of a file in the R2 bucket, creating it if it does not exist.
+        //!
+        //! R2 has no native append, so the whole file is downloaded, concatenated, and uploaded again.
+        //! The upload is conditional on the ETag of the downloaded file (`If-Match`),
+        //! or on the absence of the file (`If-None-Match: *`), and is retried up to 5 times
+        //! when another writer changed the file in between.
+        //! `Content-Type` and `Cache-Control` of the existing file are kept.
+        //!
+        //! <div class="warning">
+        //!
+        //! This is not atomic and transfers the whole file on every call,
+        //! so it is only suitable for small files such as logs or aggregates.
+        //!
+        //! </div>
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // append lines to a log
+        //!    object.append("doctest_operator_append.log", b"first\n").await?;
+        //!    object.append("doctest_operator_append.log", b"second\n").await?;
+        //!
+        //!    let bin: Vec<u8> = object.download("doctest_operator_append.log").await?;
+        //!    assert_eq!(bin, b"first\nsecond\n");
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_append.log").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("append", async {
+            let mut attempt = 1;
+            loop {
+                let current = match self
+                    .client
+                    .get_object()
+                    .set_request_payer(self.request_payer())
+                    .bucket(&self.bucket_name)
+                    .key(file_name)
+                    .send()
+                    .await
+                {
+                    Ok(object) => Some(object),
+                    Err(err) if status_code(&err) == Some(404) => None,
+                    Err(err) => return Err(get_object_error(err)),
+                };
+                let request = self
+                    .client
+                    .put_object()
+                    .set_request_payer(self.request_payer())
+                    .bucket(&self.bucket_name)
+                    .key(file_name);
+                let (request, mut body) = match current {
+                    Some(object) => {
+                        let request = request
+                            .set_content_type(object.content_type.clone())
+                            .set_cache_control(object.cache_control.clone())
+                            .set_if_match(object.e_tag.clone());
+                        (request, self.read_body(object.body).await?)
+                    }
+                    None => (
+                        request
+                            .content_type(OCTET_STREAM)
+                            .cache_control("no-cache")
+                            .if_none_match("*"),
+                        Vec::new(),
+                    ),
+                };
+                body.extend_from_slice(data);
+                match request
+                    .body(self.throttled_body(ByteStream::from(body)))
+                    .send()
+                    .await
+                {
+                    Ok(_) => return Ok(()),
+                    // 409 is returned by R2 when a conflicting conditional write is in progress
+                    Err(err)
+                        if matches!(status_code(&err), Some(409 | 412))
+                            && attempt < APPEND_MAX_ATTEMPTS =>
+                    {
+                        attempt += 1
+                    }
+                    Err(err) if matches!(status_code(&err), Some(409 | 412)) => {
+                        return Err(crate::error::OperationError::PreconditionFailed(
+                            err.to_string(),
+                        ))
+                    }
+                    Err(err) => {
+                        return Err(crate::error::OperationError::AWSSdkS3PutObjectError(
+                            err.into(),
+                        ))
+                    }
+                }
+            }
+        })
+        .await
+    }
+
+    pub async fn raw_get_object(
+        &self,
+        file_name: &str,
+    ) -> Result<GetObjectOutput, crate::error::OperationError> {
+        //! Get a file from the R2 bucket as the unmodified [GetObjectOutput] of aws-sdk-s3.
+        //!
+        //! This is an escape hatch for flows that need the streaming body together with all the headers.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_raw_get_object.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // get the raw output
+        //!    let output = object
+        //!        .raw_get_object("doctest_operator_raw_get_object.txt")
+        //!        .await?;
+        //!
+        //!    println!("{:?} {:?}", output.content_type(), output.e_tag());
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_raw_get_object.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("raw_get_object", async {
+            match self
+                .client
+                .get_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .send()
+                .await
+            {
+                Ok(object) => Ok(object),
+                Err(err) => Err(get_object_error(err)),
+            }
+        })
+        .await
+    }
+
+    pub async fn download_if_modified_since(
+        &self,
+        file_name: &str,
+        since: SystemTime,
+    ) -> Result<Option<Vec<u8>>, crate::error::OperationError> {
+        //! Download a file as binary data from the R2 bucket only if it was modified after `since`.
+        //!
+        //! Return `Ok(None)` if the file was not modified (`304 Not Modified`).
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use std::time::{Duration, SystemTime};
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download_if_modified_since.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // the file was not modified in the future
+        //!    let bin: Option<Vec<u8>> = object
+        //!        .download_if_modified_since(
+        //!            "doctest_operator_download_if_modified_since.txt",
+        //!            SystemTime::now() + Duration::from_secs(60),
+        //!        )
+        //!        .await?;
+        //!    assert_eq!(bin, None);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download_if_modified_since.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("download_if_modified_since", async {
+            let object = match self
+                .client
+                .get_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .if_modified_since(DateTime::from(since))
+                .send()
+                .await
+            {
+                Ok(object) => object,
+                Err(err) if status_code(&err) == Some(304) => return Ok(None),
+                Err(err) => return Err(get_object_error(err)),
+            };
+            let result = match self.read_body(object.body).await {
+                Ok(result) => result,
+                Err(err) => return Err(crate::error::OperationError::AWSSdkS3ByteStreamError(err)),
+            };
+            Ok(Some(result))
+        })
+        .await
+    }
+
+    pub async fn download_if_unmodified_since(
+        &self,
+        file_name: &str,
+        since: SystemTime,
+    ) -> Result<Vec<u8>, crate::error::OperationError> {
+        //! Download a file as binary data from the R2 bucket only if it was not modified after `since`.
+        //!
+        //! Return [OperationError::PreconditionFailed](crate::error::OperationError::PreconditionFailed)
+        //! if the file was modified (`412 Precondition Failed`).
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use std::time::{Duration, SystemTime};
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download_if_unmodified_since.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // the file was not modified after a minute from now
+        //!    let bin: Vec<u8> = object
+        //!        .download_if_unmodified_since(
+        //!            "doctest_operator_download_if_unmodified_since.txt",
+        //!            SystemTime::now() + Duration::from_secs(60),
+        //!        )
+        //!        .await?;
+        //!
+        //!    println!("{:?}", bin);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download_if_unmodified_since.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("download_if_unmodified_since", async {
+            let object = match self
+                .client
+                .get_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .if_unmodified_since(DateTime::from(since))
+                .send()
+                .await
+            {
+                Ok(object) => object,
+                Err(err) if status_code(&err) == Some(412) => {
+                    return Err(crate::error::OperationError::PreconditionFailed(
+                        err.to_string(),
+                    ))
+                }
+                Err(err) => return Err(get_object_error(err)),
+            };
+            let result = match self.read_body(object.body).await {
+                Ok(result) => result,
+                Err(err) => return Err(crate::error::OperationError::AWSSdkS3ByteStreamError(err)),
+            };
+            Ok(result)
+        })
+        .await
+    }
+
+    pub async fn download_with_overrides(
+        &self,
+        file_name: &str,
+        overrides: &ResponseOverrides,
+    ) -> Result<DownloadedObject, crate::error::OperationError> {
+        //! Download a file from the R2 bucket with overridden response headers.
+        //!
+        //! The returned [DownloadedObject] holds the binary data and the (overridden) headers.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::options::ResponseOverrides;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download_with_overrides.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // download binary data as an attachment
+        //!    let overrides = ResponseOverrides::new()
+        //!        .set_content_type("application/octet-stream")
+        //!        .set_content_disposition("attachment; filename=\"hello.txt\"");
+        //!    let downloaded = object
+        //!        .download_with_overrides("doctest_operator_download_with_overrides.txt", &overrides)
+        //!        .await?;
+        //!
+        //!    println!("{:?}", downloaded.content_type);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download_with_overrides.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("download_with_overrides", async {
+            let object = match self
+                .client
+                .get_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .set_response_content_type(overrides.content_type.clone())
+                .set_response_content_disposition(overrides.content_disposition.clone())
+                .set_response_cache_control(overrides.cache_control.clone())
+                .send()
+                .await
+            {
+                Ok(object) => object,
+                Err(err) => return Err(get_object_error(err)),
+            };
+            let content_type = object.content_type.clone();
+            let content_disposition = object.content_disposition.clone();
+            let cache_control = object.cache_control.clone();
+            let body = match self.read_body(object.body).await {
+                Ok(result) => result,
+                Err(err) => return Err(crate::error::OperationError::AWSSdkS3ByteStreamError(err)),
+            };
+            Ok(DownloadedObject {
+                body,
+                content_type,
+                content_disposition,
+                cache_control,
+            })
+        })
+        .await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload_files(
+        &self,
+        items: &[(&str, &str, &str)],
+    ) -> Result<Vec<Result<(), crate::error::OperationError>>, crate::error::OperationError> {
+        //! Upload multiple files to the R2 bucket concurrently.
+        //!
+        //! Each item is a tuple of `(file name (key), file path, mime type)`.
+        //! At most 8 files are uploaded at the same time.
+        //! Return the result of each upload in the same order as `items`.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // upload files
+        //!    let results = object
+        //!        .upload_files(&[
+        //!            ("doctest_operator_upload_files.jpg", "./data/sample.jpg", "image/jpeg"),
+        //!            ("doctest_operator_upload_files.md", "./data/LICENSE.md", "text/markdown"),
+        //!        ])
+        //!        .await?;
+        //!
+        //!    for result in results {
+        //!        result?;
+        //!    }
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_files.jpg").await?;
+        //!    object.delete("doctest_operator_upload_files.md").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_files", async {
+            // Map indices instead of borrowed items, so that the future stays `Send`.
+            let uploads = (0..items.len()).map(|index| {
+                let (file_name, file_path, mime_type) = items[index];
+                self.upload_file(file_name, mime_type, file_path, None)
+            });
+            let results = futures::stream::iter(uploads)
+                .buffered(MAX_CONCURRENCY)
+                .collect()
+                .await;
+            Ok(results)
+        })
+        .await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload_file_with_stats(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        file_path: impl AsRef<Path>,
+        cache_control: Option<&str>,
+    ) -> Result<TransferStats, crate::error::OperationError> {
+        //! Upload a file to the R2 bucket and return the observed [TransferStats].
+        //!
+        //! The duration includes reading the file from the local disk.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // upload file
+        //!    let stats = object
+        //!        .upload_file_with_stats("doctest_operator_upload_file_with_stats.jpg", "image/jpeg", "./data/sample.jpg", None)
+        //!        .await?;
+        //!
+        //!    println!("{} bytes/sec", stats.throughput_bytes_per_sec);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_file_with_stats.jpg").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_file_with_stats", async {
+            let file_path = file_path.as_ref();
+            let started = Instant::now();
+            let bytes = tokio::fs::metadata(file_path).await?.len();
+            self.upload_file(file_name, mime_type, file_path, cache_control)
+                .await?;
+            Ok(TransferStats::new(bytes, started.elapsed()))
+        })
+        .await
+    }
+
+    pub async fn upload_binary_with_stats(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        binary: &[u8],
+        cache_control: Option<&str>,
+    ) -> Result<TransferStats, crate::error::OperationError> {
+        //! Upload binary data to the R2 bucket and return the observed [TransferStats].
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // upload binary data
+        //!    let stats = object
+        //!        .upload_binary_with_stats("doctest_operator_upload_binary_with_stats.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    println!("{} bytes/sec", stats.throughput_bytes_per_sec);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_binary_with_stats.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_binary_with_stats", async {
+            let started = Instant::now();
+            self.upload_binary(file_name, mime_type, binary, cache_control)
+                .await?;
+            Ok(TransferStats::new(binary.len() as u64, started.elapsed()))
+        })
+        .await
+    }
+
+    pub async fn download_with_stats(
+        &self,
+        file_name: &str,
+    ) -> Result<(Vec<u8>, TransferStats), crate::error::OperationError> {
+        //! Download a file as binary data from the R2 bucket and return the observed [TransferStats].
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download_with_stats.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // download binary data
+        //!    let (bin, stats) = object
+        //!        .download_with_stats("doctest_operator_download_with_stats.txt")
+        //!        .await?;
+        //!
+        //!    println!("{} bytes in {:?}", bin.len(), stats.duration);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download_with_stats.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("download_with_stats", async {
+            let started = Instant::now();
+            let binary = self.download(file_name).await?;
+            let stats = TransferStats::new(binary.len() as u64, started.elapsed());
+            Ok((binary, stats))
+        })
+        .await
+    }
+
+    #[cfg(feature = "cancellation")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload_file_with_cancel(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        file_path: impl AsRef<Path>,
+        cache_control: Option<&str>,
+        token: &CancellationToken,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Upload a file to the R2 bucket, aborting when `token` is cancelled.
+        //!
+        //! Return [OperationError::Cancelled](crate::error::OperationError::Cancelled) if the token is cancelled before the upload completes.
+        //!
+        //! Requires the `cancellation` feature.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use tokio_util::sync::CancellationToken;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    let token = CancellationToken::new();
+        //!
+        //!    // upload file
+        //!    object
+        //!        .upload_file_with_cancel("doctest_operator_upload_file_with_cancel.jpg", "image/jpeg", "./data/sample.jpg", None, &token)
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_file_with_cancel.jpg").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_file_with_cancel", async {
+            tokio::select! {
+                biased;
+                _ = token.cancelled() => Err(crate::error::OperationError::Cancelled),
+                result = self.upload_file(file_name, mime_type, file_path, cache_control) => result,
+            }
+        })
+        .await
+    }
+
+    #[cfg(feature = "cancellation")]
+    pub async fn upload_binary_with_cancel(
+        &self,
+        file_name: &str,
+        mime_type: &str,
+        binary: &[u8],
+        cache_control: Option<&str>,
+        token: &CancellationToken,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Upload binary data to the R2 bucket, aborting when `token` is cancelled.
+        //!
+        //! Return [OperationError::Cancelled](crate::error::OperationError::Cancelled) if the token is cancelled before the upload completes.
+        //! If the upload was a multipart upload, it is aborted in the background.
+        //!
+        //! Requires the `cancellation` feature.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use tokio_util::sync::CancellationToken;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    let token = CancellationToken::new();
+        //!
+        //!    // upload binary data
+        //!    object
+        //!        .upload_binary_with_cancel("doctest_operator_upload_binary_with_cancel.txt", "test/plain", b"Hello, World!", None, &token)
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_upload_binary_with_cancel.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_binary_with_cancel", async {
+            tokio::select! {
+                biased;
+                _ = token.cancelled() => Err(crate::error::OperationError::Cancelled),
+                result = self.upload_binary(file_name, mime_type, binary, cache_control) => result,
+            }
+        })
+        .await
+    }
+
+    #[cfg(feature = "cancellation")]
+    pub async fn download_with_cancel(
+        &self,
+        file_name: &str,
+        token: &CancellationToken,
+    ) -> Result<Vec<u8>, crate::error::OperationError> {
+        //! Download a file as binary data from the R2 bucket, aborting when `token` is cancelled.
+        //!
+        //! Return [OperationError::Cancelled](crate::error::OperationError::Cancelled) if the token is cancelled before the download completes.
+        //!
+        //! Requires the `cancellation` feature.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use tokio_util::sync::CancellationToken;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download_with_cancel.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // cancel the download before it starts
+        //!    let token = CancellationToken::new();
+        //!    token.cancel();
+        //!    let result = object
+        //!        .download_with_cancel("doctest_operator_download_with_cancel.txt", &token)
+        //!        .await;
+        //!    assert!(result.is_err());
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download_with_cancel.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("download_with_cancel", async {
+            tokio::select! {
+                biased;
+                _ = token.cancelled() => Err(crate::error::OperationError::Cancelled),
+                result = self.download(file_name) => result,
+            }
+        })
+        .await
+    }
+
+    pub async fn download_range(
+        &self,
+        file_name: &str,
+        range: Option<(u64, u64)>,
+    ) -> Result<DownloadResult, crate::error::OperationError> {
+        //! Download a file as binary data from the R2 bucket, optionally only the inclusive byte range `(start, end)`.
+        //!
+        //! [DownloadResult::is_partial] tells whether the server honored the range,
+        //! so a full body is never silently treated as a slice.
+        //!
+        //! An inverted range (`start > end`) returns [OperationError::InvalidRange](crate::error::OperationError::InvalidRange)
+        //! without a request. A range starting beyond the end of the file (`416 Range Not Satisfiable`)
+        //! also returns it, with the size of the file if the server reported it.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download_range.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // download "World"
+        //!    let result = object
+        //!        .download_range("doctest_operator_download_range.txt", Some((7, 11)))
+        //!        .await?;
+        //!
+        //!    if result.is_partial {
+        //!        assert_eq!(result.bytes, b"World");
+        //!    }
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download_range.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("download_range", async {
+            let header = match range {
+                Some((start, end)) if start <= end => Some(range_header(start, end - start + 1)?),
+                Some((start, end)) => {
+                    return Err(crate::error::OperationError::InvalidRange {
+                        start,
+                        end,
+                        size: None,
+                    })
+                }
+                None => None,
+            };
+            let object = match self
+                .client
+                .get_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .set_range(header)
+                .send()
+                .await
+            {
+                Ok(object) => object,
+                Err(err) if status_code(&err) == Some(416) => {
+                    let (start, end) = range.unwrap_or_default();
+                    return Err(crate::error::OperationError::InvalidRange {
+                        start,
+                        end,
+                        size: unsatisfied_range_size(&err),
+                    });
+                }
+                Err(err) => return Err(get_object_error(err)),
+            };
+            let content_range = object.content_range.clone();
+            let bytes = match self.read_body(object.body).await {
+                Ok(result) => result,
+                Err(err) => return Err(crate::error::OperationError::AWSSdkS3ByteStreamError(err)),
+            };
+            Ok(DownloadResult {
+                bytes,
+                is_partial: content_range.is_some(),
+                content_range,
+            })
+        })
+        .await
+    }
+
+    pub async fn download_part(
+        &self,
+        file_name: &str,
+        part_number: i32,
+    ) -> Result<Vec<u8>, crate::error::OperationError> {
+        //! Download a single part of a multipart uploaded file from the R2 bucket (`partNumber`, starting at 1).
+        //!
+        //! The parts have the sizes used when the file was uploaded, so the file can be downloaded
+        //! in parallel with one request per part. A file uploaded in a single request has only part 1.
+        //! Return [OperationError::NotSupported](crate::error::OperationError::NotSupported)
+        //! if the server ignored `partNumber` and returned the whole file instead of the part.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download_part.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // a single part upload has only part 1
+        //!    let part = object.download_part("doctest_operator_download_part.txt", 1).await?;
+        //!    assert_eq!(part, b"Hello, World!");
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download_part.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("download_part", async {
+            let object = match self
+                .client
+                .get_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .part_number(part_number)
+                .send()
+                .await
+            {
+                Ok(object) => object,
+                Err(err) => return Err(get_object_error(err)),
+            };
+            if part_number > 1 && object.content_range.is_none() && object.parts_count.is_none() {
+                return Err(crate::error::OperationError::NotSupported(format!(
+                    "The server ignored partNumber {} and returned the whole file.",
+                    part_number
+                )));
+            }
+            match self.read_body(object.body).await {
+                Ok(result) => Ok(result),
+                Err(err) => Err(crate::error::OperationError::AWSSdkS3ByteStreamError(err)),
+            }
+        })
+        .await
+    }
+
+    pub async fn download_stream(
+        &self,
+        file_name: &str,
+    ) -> Result<
+        impl Stream<Item = Result<Bytes, crate::error::OperationError>> + Send + 'static,
+        crate::error::OperationError,
+    > {
+        //! Download a file from the R2 bucket as a stream of chunks.
+        //!
+        //! The body is not buffered, so it can be used as a streaming response body
+        //! (e.g. of axum or actix-web) with constant memory.
+        //! The stream does not borrow the [Operator], and each chunk is throttled under the rate limit, if any.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use futures::StreamExt;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download_stream.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    let mut stream = Box::pin(object.download_stream("doctest_operator_download_stream.txt").await?);
+        //!    let mut bin: Vec<u8> = Vec::new();
+        //!    while let Some(chunk) = stream.next().await {
+        //!        bin.extend_from_slice(&chunk?);
+        //!    }
+        //!    assert_eq!(bin, b"Hello, World!");
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download_stream.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        let deadline = self.deadline();
+        self.instrument("download_stream", async {
+            let object = match self
+                .client
+                .get_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .send()
+                .await
+            {
+                Ok(object) => object,
+                Err(err) => return Err(get_object_error(err)),
+            };
+            let rate_limiter = self.rate_limiter.clone();
+            Ok(futures::stream::unfold(Some(object.body), move |body| {
+                let rate_limiter = rate_limiter.clone();
+                async move {
+                    let mut body = body?;
+                    let next = match deadline {
+                        Some((deadline, timeout)) => {
+                            match tokio::time::timeout_at(deadline, body.try_next()).await {
+                                Ok(next) => next,
+                                Err(_) => {
+                                    return Some((
+                                        Err(crate::error::OperationError::Timeout(timeout)),
+                                        None,
+                                    ))
+                                }
+                            }
+                        }
+                        None => body.try_next().await,
+                    };
+                    match next {
+                        Ok(Some(chunk)) => {
+                            if let Some(rate_limiter) = rate_limiter {
+                                rate_limiter.acquire(chunk.len() as u64).await;
+                            }
+                            Some((Ok(chunk), Some(body)))
+                        }
+                        Ok(None) => None,
+                        // end the stream after the error
+                        Err(err) => Some((Err(err.into()), None)),
+                    }
+                }
+            }))
+        })
+        .await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn download_to_file(
+        &self,
+        file_name: &str,
+        file_path: impl AsRef<Path>,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Download a file from the R2 bucket and save it to `file_path` atomically.
+        //!
+        //! The body is streamed to a temporary file in the same directory, which is renamed to
+        //! `file_path` only after the whole body has been written, so readers never observe a
+        //! half-written file. The temporary file is removed on error or cancellation.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download_to_file.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // save to a local file
+        //!    object
+        //!        .download_to_file("doctest_operator_download_to_file.txt", "./target/doctest_operator_download_to_file.txt")
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download_to_file.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("download_to_file", async {
+            let path = file_path.as_ref();
+            let mut temp = RemoveOnDrop {
+                path: temp_path(path),
+                armed: true,
+            };
+            let mut file = File::create(&temp.path).await?;
+            let mut stream = Box::pin(self.download_stream(file_name).await?);
+            while let Some(chunk) = stream.next().await {
+                file.write_all(&chunk?).await?;
+            }
+            file.sync_all().await?;
+            drop(file);
+            tokio::fs::rename(&temp.path, path).await?;
+            temp.armed = false;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn verify(&self, file_name: &str) -> Result<bool, crate::error::OperationError> {
+        //! Check the integrity of a file stored in the R2 bucket.
+        //!
+        //! The file is downloaded as a stream, its MD5 based ETag is recomputed and compared with
+        //! the ETag returned with the body, so the whole file is never held in memory.
+        //! A multipart ETag (`"<md5>-<parts>"`) is recomputed with the part sizes this crate and
+        //! common tools use. `false` is returned if the ETag does not match, or if it is not
+        //! MD5 based (e.g. an unknown part size), so it cannot be checked.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_verify.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // check the stored file
+        //!    assert!(object.verify("doctest_operator_verify.txt").await?);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_verify.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("verify", async {
+            let object = match self
+                .client
+                .get_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .send()
+                .await
+            {
+                Ok(object) => object,
+                Err(err) => return Err(get_object_error(err)),
+            };
+            let size = object.content_length().unwrap_or(0).max(0) as u64;
+            let mut matcher = match object.e_tag() {
+                Some(e_tag) => EtagMatcher::new(size, e_tag),
+                None => return Ok(false),
+            };
+            if !matcher.is_comparable() {
+                return Ok(false);
+            }
+            let mut body = object.body;
+            let mut received = 0;
+            loop {
+                match body.try_next().await {
+                    Ok(Some(chunk)) => {
+                        self.throttle(chunk.len() as u64).await;
+                        received += chunk.len() as u64;
+                        matcher.update(&chunk);
+                    }
+                    Ok(None) => return Ok(received == size && matcher.matches()),
+                    Err(err) => {
+                        return Err(crate::error::OperationError::AWSSdkS3ByteStreamError(err))
+                    }
+                }
+            }
+        })
+        .await
+    }
+
+    pub async fn sniff(
+        &self,
+        file_name: &str,
+        n: usize,
+    ) -> Result<Vec<u8>, crate::error::OperationError> {
+        //! Download only the first `n` bytes of a file from the R2 bucket (`Range: bytes=0-(n-1)`),
+        //! e.g. to read the magic bytes or the header of a large file.
+        //!
+        //! A file shorter than `n` bytes is returned whole, and an empty file returns an empty vector.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_sniff.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    let head: Vec<u8> = object.sniff("doctest_operator_sniff.txt", 5).await?;
+        //!    assert_eq!(head, b"Hello");
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_sniff.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("sniff", async {
+            if n == 0 {
+                return Ok(Vec::new());
+            }
+            match self
+                .download_range(file_name, Some((0, n as u64 - 1)))
+                .await
+            {
+                Ok(mut result) => {
+                    // the server may ignore the range and return the whole file
+                    result.bytes.truncate(n);
+                    Ok(result.bytes)
+                }
+                // a range of an empty file cannot be satisfied
+                Err(crate::error::OperationError::InvalidRange { .. }) => Ok(Vec::new()),
+                Err(err) => Err(err),
+            }
+        })
+        .await
+    }
+
+    pub async fn download_ranges(
+        &self,
+        file_name: &str,
+        ranges: &[(u64, u64)],
+    ) -> Result<Vec<Vec<u8>>, crate::error::OperationError> {
+        //! Download several inclusive byte ranges `(start, end)` of a file from the R2 bucket.
+        //!
+        //! R2 does not support multi-range requests (`multipart/byteranges`),
+        //! so each range is downloaded with its own request, up to 8 at once.
+        //! The results are in the same order as `ranges`.
+        //! The first range is requested alone: if the server ignores it and returns the whole file,
+        //! every range is cut out of that body, so the file is downloaded only once.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_download_ranges.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    let ranges: Vec<Vec<u8>> = object
+        //!        .download_ranges("doctest_operator_download_ranges.txt", &[(0, 4), (7, 11)])
+        //!        .await?;
+        //!    assert_eq!(ranges, vec![b"Hello".to_vec(), b"World".to_vec()]);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_download_ranges.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("download_ranges", async {
+            let first = match ranges.first() {
+                Some(&first) => first,
+                None => return Ok(Vec::new()),
+            };
+            // the first range is downloaded alone, to find out whether the server supports ranges
+            let result = self.download_range(file_name, Some(first)).await?;
+            if !result.is_partial {
+                return ranges
+                    .iter()
+                    .map(|&(start, end)| cut_range(&result.bytes, start, end))
+                    .collect();
+            }
+            let mut parts = vec![result.bytes];
+            // Map indices instead of borrowed ranges, so that the future stays `Send`.
+            let downloads = (1..ranges.len()).map(|index| async move {
+                let (start, end) = ranges[index];
+                let result = self.download_range(file_name, Some((start, end))).await?;
+                match result.is_partial {
+                    true => Ok(result.bytes),
+                    false => cut_range(&result.bytes, start, end),
+                }
+            });
+            let mut downloads = futures::stream::iter(downloads).buffered(MAX_CONCURRENCY);
+            while let Some(part) = downloads.next().await {
+                parts.push(part?);
+            }
+            Ok(parts)
+        })
+        .await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn get_range_stream(
+        &self,
+        file_name: &str,
+        start: u64,
+        len: u64,
+    ) -> Result<impl AsyncRead, crate::error::OperationError> {
+        //! Get a byte range of a file from the R2 bucket as an async reader.
+        //!
+        //! The range starts at `start` and is `len` bytes long.
+        //! The body is not buffered, so it can be piped directly to another writer.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use tokio::io::AsyncReadExt;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_get_range_stream.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // get "World" as an async reader
+        //!    let mut reader = object
+        //!        .get_range_stream("doctest_operator_get_range_stream.txt", 7, 5)
+        //!        .await?;
+        //!    let mut buffer = Vec::new();
+        //!    reader.read_to_end(&mut buffer).await.unwrap();
+        //!    assert_eq!(buffer, b"World");
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_get_range_stream.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        let deadline = self.deadline();
+        self.instrument("get_range_stream", async {
+            let range = range_header(start, len)?;
+            let object = match self
+                .client
+                .get_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .range(range)
+                .send()
+                .await
+            {
+                Ok(object) => object,
+                Err(err) => return Err(get_object_error(err)),
+            };
+            Ok(DeadlineReader {
+                reader: Box::pin(object.body.into_async_read()),
+                deadline: deadline.map(|(deadline, timeout)| {
+                    (Box::pin(tokio::time::sleep_until(deadline)), timeout)
+                }),
+            })
+        })
+        .await
+    }
+
+    pub async fn delete(&self, file_name: &str) -> Result<(), crate::error::OperationError> {
+        //! Delete a file from the R2 bucket.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_delete.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // delete file
+        //!    object.delete("doctest_operator_delete.txt").await?;
+        //!
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("delete", async {
+            self.delete_from(&self.bucket_name, file_name).await
+        })
+        .await
+    }
+
+    pub async fn delete_from(
+        &self,
+        bucket_name: &str,
+        file_name: &str,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Delete a file from the given R2 bucket instead of the configured one.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    let other_bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!
+        //!    object
+        //!        .upload_binary_to(&other_bucket_name, "doctest_operator_delete_from.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // delete file
+        //!    object.delete_from(&other_bucket_name, "doctest_operator_delete_from.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("delete_from", async {
+            match &self
+                .client
+                .delete_object()
+                .set_request_payer(self.request_payer())
+                .bucket(bucket_name)
+                .key(file_name)
+                .send()
+                .await
+            {
+                Ok(_) => (),
+                Err(err) => {
+                    return Err(crate::error::OperationError::AWSSdkS3DeleteObjectError(
+                        err.into(),
+                    ))
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn delete_version(
+        &self,
+        file_name: &str,
+        version_id: &str,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Delete a specific version of a file from the R2 bucket.
+        //!
+        //! In a versioned bucket, [Operator::delete] only adds a delete marker,
+        //! while this permanently removes the version `version_id`.
+        //! The version ID `null` is the version uploaded while versioning was not enabled.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_delete_version.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // delete the version uploaded without versioning
+        //!    object.delete_version("doctest_operator_delete_version.txt", "null").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("delete_version", async {
+            match self
+                .client
+                .delete_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .version_id(version_id)
+                .send()
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(err) => Err(crate::error::OperationError::AWSSdkS3DeleteObjectError(
+                    err.into(),
+                )),
+            }
+        })
+        .await
+    }
+
+    pub async fn copy_object(
+        &self,
+        source_file_name: &str,
+        destination_file_name: &str,
+    ) -> Result<CopiedObject, crate::error::OperationError> {
+        //! Copy a file to another key in the R2 bucket, and return the ETag and the size of the copy.
+        //!
+        //! The data is copied on the server side, so it is not downloaded.
+        //! The ETag is taken from the `CopyObject` response, and the size from a `HeadObject` of the copy.
+        //! Use [Operator::copy_object_with_options] to set new headers on the copy.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_copy_object.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // copy file
+        //!    let copied: cf_r2_sdk::object::CopiedObject = object
+        //!        .copy_object("doctest_operator_copy_object.txt", "doctest_operator_copy_object_copied.txt")
+        //!        .await?;
+        //!    assert_eq!(copied.size, 13);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_copy_object.txt").await?;
+        //!    object.delete("doctest_operator_copy_object_copied.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("copy_object", async {
+            self.copy_and_head(source_file_name, destination_file_name, None)
+                .await
+        })
+        .await
+    }
+
+    pub async fn copy_objects(
+        &self,
+        pairs: &[(&str, &str)],
+    ) -> Result<Vec<Result<(), crate::error::OperationError>>, crate::error::OperationError> {
+        //! Copy multiple files to other keys in the R2 bucket concurrently.
+        //!
+        //! Each pair is a tuple of `(source file name, destination file name)`.
+        //! The data is copied on the server side, and at most 8 files are copied at the same time.
+        //! Return the result of each copy in the same order as `pairs`,
+        //! and a failure of one copy does not affect the others.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_copy_objects_a.txt", "text/plain", b"a", None)
+        //!        .await?;
+        //!    object
+        //!        .upload_binary("doctest_operator_copy_objects_b.txt", "text/plain", b"b", None)
+        //!        .await?;
+        //!
+        //!    // copy the files
+        //!    let results = object
+        //!        .copy_objects(&[
+        //!            ("doctest_operator_copy_objects_a.txt", "doctest_operator_copy_objects_a_copy.txt"),
+        //!            ("doctest_operator_copy_objects_b.txt", "doctest_operator_copy_objects_b_copy.txt"),
+        //!        ])
+        //!        .await?;
+        //!
+        //!    for result in results {
+        //!        result?;
+        //!    }
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_copy_objects_a.txt").await?;
+        //!    object.delete("doctest_operator_copy_objects_b.txt").await?;
+        //!    object.delete("doctest_operator_copy_objects_a_copy.txt").await?;
+        //!    object.delete("doctest_operator_copy_objects_b_copy.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("copy_objects", async {
+            // Map indices instead of borrowed pairs, so that the future stays `Send`.
+            let copies = (0..pairs.len()).map(|index| async move {
+                let (source_file_name, destination_file_name) = pairs[index];
+                self.copy(source_file_name, destination_file_name, None)
+                    .await
+                    .map(|_| ())
+            });
+            let results = futures::stream::iter(copies)
+                .buffered(MAX_CONCURRENCY)
+                .collect()
+                .await;
+            Ok(results)
+        })
+        .await
+    }
+
+    /// Copy `source_file_name` to `destination_file_name` and return the ETag of the copy, if any.
+    ///
+    /// If `source_e_tag` is set, the copy is made only if the source still has the ETag.
+    pub(crate) async fn copy(
+        &self,
+        source_file_name: &str,
+        destination_file_name: &str,
+        source_e_tag: Option<&str>,
+    ) -> Result<Option<String>, crate::error::OperationError> {
+        match self
+            .client
+            .copy_object()
+            .set_request_payer(self.request_payer())
+            .bucket(&self.bucket_name)
+            .copy_source(copy_source(&self.bucket_name, source_file_name))
+            .key(destination_file_name)
+            .set_copy_source_if_match(source_e_tag.map(quote_etag))
+            .send()
+            .await
+        {
+            Ok(output) => Ok(output
+                .copy_object_result()
+                .and_then(|result| result.e_tag())
+                .map(unquote_etag)),
+            Err(err) if status_code(&err) == Some(412) => Err(
+                crate::error::OperationError::PreconditionFailed(err.to_string()),
+            ),
+            Err(err) => Err(crate::error::OperationError::AWSSdkS3CopyObjectError(
+                err.into(),
+            )),
+        }
+    }
+
+    /// Delete `file_name`, only if it still has `e_tag` when it is set (`If-Match`).
+    async fn delete_if_match(
+        &self,
+        file_name: &str,
+        e_tag: Option<&str>,
+    ) -> Result<(), SdkError<DeleteObjectError, HttpResponse>> {
+        self.client
+            .delete_object()
+            .set_request_payer(self.request_payer())
+            .bucket(&self.bucket_name)
+            .key(file_name)
+            .set_if_match(e_tag.map(quote_etag))
+            .send()
+            .await
+            .map(|_| ())
+    }
+
+    /// Copy and `HEAD` the copy to build the [CopiedObject].
+    async fn copy_and_head(
+        &self,
+        source_file_name: &str,
+        destination_file_name: &str,
+        source_e_tag: Option<&str>,
+    ) -> Result<CopiedObject, crate::error::OperationError> {
+        let e_tag = self
+            .copy(source_file_name, destination_file_name, source_e_tag)
+            .await?;
+        let destination = self.head(destination_file_name).await?;
+        Ok(CopiedObject {
+            e_tag: e_tag.or_else(|| destination.e_tag().map(unquote_etag)),
+            size: destination.content_length().unwrap_or(0) as u64,
+        })
+    }
+
+    pub async fn copy_object_if_match(
+        &self,
+        source_file_name: &str,
+        destination_file_name: &str,
+        source_e_tag: &str,
+    ) -> Result<CopiedObject, crate::error::OperationError> {
+        //! Copy a file to another key in the R2 bucket only if the source still has `source_e_tag`
+        //! (`x-amz-copy-source-if-match`).
+        //! `source_e_tag` can be given with or without the surrounding double quotes.
+        //!
+        //! Return [OperationError::PreconditionFailed](crate::error::OperationError::PreconditionFailed)
+        //! if the source was changed (`412 Precondition Failed`), so a newer version is never copied by mistake.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_copy_object_if_match.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!    let e_tag = object
+        //!        .head_object("doctest_operator_copy_object_if_match.txt")
+        //!        .await?
+        //!        .e_tag
+        //!        .expect("ETag not found.");
+        //!
+        //!    // copy file only if it has not been changed
+        //!    object
+        //!        .copy_object_if_match(
+        //!            "doctest_operator_copy_object_if_match.txt",
+        //!            "doctest_operator_copy_object_if_match_copied.txt",
+        //!            &e_tag,
+        //!        )
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_copy_object_if_match.txt").await?;
+        //!    object.delete("doctest_operator_copy_object_if_match_copied.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("copy_object_if_match", async {
+            self.copy_and_head(source_file_name, destination_file_name, Some(source_e_tag))
+                .await
+        })
+        .await
+    }
+
+    pub async fn copy_object_with_options(
+        &self,
+        source_file_name: &str,
+        destination_file_name: &str,
+        options: &UploadOptions,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Copy a file to another key in the R2 bucket, replacing its headers with `options`.
+        //!
+        //! The data is copied on the server side, so it is not downloaded.
+        //! The `Content-Type` of the source file is kept.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::options::UploadOptions;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_copy_object_with_options.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // copy file with a long cache
+        //!    let options = UploadOptions::new().set_cache_control("public, max-age=31536000, immutable");
+        //!    object
+        //!        .copy_object_with_options(
+        //!            "doctest_operator_copy_object_with_options.txt",
+        //!            "doctest_operator_copy_object_with_options_copied.txt",
+        //!            &options,
+        //!        )
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_copy_object_with_options.txt").await?;
+        //!    object.delete("doctest_operator_copy_object_with_options_copied.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("copy_object_with_options", async {
+            // Replacing the metadata also replaces `Content-Type`, so carry it over from the source.
+            let source = self.head(source_file_name).await?;
+            let request = self
+                .client
+                .copy_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .copy_source(copy_source(&self.bucket_name, source_file_name))
+                .key(destination_file_name)
+                .set_content_type(source.content_type().map(str::to_owned));
+            match options.apply_copy(request).send().await {
+                Ok(_) => Ok(()),
+                Err(err) => Err(crate::error::OperationError::AWSSdkS3CopyObjectError(
+                    err.into(),
+                )),
+            }
+        })
+        .await
+    }
+
+    pub async fn copy_object_verified(
+        &self,
+        source_file_name: &str,
+        destination_file_name: &str,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Copy a file to another key in the R2 bucket and verify the copied file.
+        //!
+        //! After the copy, the ETag of the destination is compared with the ETag of the source.
+        //! For a source uploaded with multipart upload, the ETags differ by design, so the sizes are compared instead.
+        //! Return [OperationError::IntegrityMismatch](crate::error::OperationError::IntegrityMismatch) on mismatch.
+        //!
+        //! The copy is made only if the source still has the ETag read before the copy
+        //! (`x-amz-copy-source-if-match`), so the copy is always compared with the version it was made from.
+        //! If the source was overwritten in between, nothing is copied and
+        //! [OperationError::PreconditionFailed](crate::error::OperationError::PreconditionFailed) is returned.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_copy_object_verified.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // copy file and verify it
+        //!    object
+        //!        .copy_object_verified("doctest_operator_copy_object_verified.txt", "doctest_operator_copy_object_verified_copied.txt")
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_copy_object_verified.txt").await?;
+        //!    object.delete("doctest_operator_copy_object_verified_copied.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("copy_object_verified", async {
+            let source = self.head(source_file_name).await?;
+            let source_e_tag = source.e_tag().map(unquote_etag);
+            match self
+                .copy(
+                    source_file_name,
+                    destination_file_name,
+                    source_e_tag.as_deref(),
+                )
+                .await
+            {
+                Ok(_) => (),
+                Err(crate::error::OperationError::PreconditionFailed(message)) => {
+                    return Err(crate::error::OperationError::PreconditionFailed(format!(
+                        "{} was modified before the copy. {}",
+                        source_file_name, message
+                    )))
+                }
+                Err(err) => return Err(err),
+            }
+            let destination = self.head(destination_file_name).await?;
+
+            let source_e_tag = source.e_tag().unwrap_or_default();
+            if !source_e_tag.contains('-') {
+                let destination_e_tag = destination.e_tag().unwrap_or_default();
+                if source_e_tag != destination_e_tag {
+                    return Err(crate::error::OperationError::IntegrityMismatch {
+                        expected: unquote_etag(source_e_tag),
+                        actual: unquote_etag(destination_e_tag),
+                    });
+                }
+            } else if source.content_length() != destination.content_length() {
+                return Err(crate::error::OperationError::IntegrityMismatch {
+                    expected: format!("{} bytes", source.content_length().unwrap_or(0)),
+                    actual: format!("{} bytes", destination.content_length().unwrap_or(0)),
+                });
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn move_object(
+        &self,
+        source_file_name: &str,
+        destination_file_name: &str,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Move a file to another key in the R2 bucket.
+        //!
+        //! R2 has no native move, so this copies the file and then deletes the source.
+        //! The two steps are not atomic, but both are conditional on the ETag of the source read
+        //! before the copy, so a concurrent change of the source aborts the move:
+        //!
+        //! - The copy is made with `x-amz-copy-source-if-match`. If the source was changed before
+        //!   the copy, nothing is changed.
+        //! - The source is deleted with `If-Match`. If the source was changed after the copy,
+        //!   the outdated copy is deleted (with `If-Match` on its ETag) and the source is kept.
+        //! - If the source was already deleted, e.g. moved by another process, the copy is kept.
+        //!
+        //! In these cases, [OperationError::PreconditionFailed](crate::error::OperationError::PreconditionFailed)
+        //! is returned, so when two processes move the same file, only one of them succeeds.
+        //! If the delete fails for another reason after a successful copy, the file exists at both keys and
+        //! [OperationError::MoveDeleteFailed](crate::error::OperationError::MoveDeleteFailed) is returned,
+        //! so the caller can retry [Operator::delete] on the source.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_move_object.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // move file
+        //!    object
+        //!        .move_object("doctest_operator_move_object.txt", "doctest_operator_move_object_moved.txt")
+        //!        .await?;
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_move_object_moved.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("move_object", async {
+            let source_e_tag = self.head(source_file_name).await?.e_tag().map(unquote_etag);
+            let copied_e_tag = self
+                .copy(
+                    source_file_name,
+                    destination_file_name,
+                    source_e_tag.as_deref(),
+                )
+                .await?;
+            match self
+                .delete_if_match(source_file_name, source_e_tag.as_deref())
+                .await
+            {
+                Ok(()) => Ok(()),
+                Err(err) if status_code(&err) == Some(412) => {
+                    // the copy is an outdated version of the source, so it is removed unless it was overwritten
+                    if let Some(copied_e_tag) = copied_e_tag {
+                        let _ = self
+                            .delete_if_match(destination_file_name, Some(&copied_e_tag))
+                            .await;
+                    }
+                    Err(crate::error::OperationError::PreconditionFailed(format!(
+                        "{} was modified during the move. {}",
+                        source_file_name, err
+                    )))
+                }
+                Err(err) if status_code(&err) == Some(404) => {
+                    Err(crate::error::OperationError::PreconditionFailed(format!(
+                        "{} was deleted or moved by another process during the move. {}",
+                        source_file_name, err
+                    )))
+                }
+                Err(err) => Err(crate::error::OperationError::MoveDeleteFailed {
+                    from: source_file_name.to_string(),
+                    copied_to: destination_file_name.to_string(),
+                    message: crate::error::OperationError::AWSSdkS3DeleteObjectError(err.into())
+                        .to_string(),
+                }),
+            }
+        })
+        .await
+    }
+
+    pub async fn delete_objects(
+        &self,
+        file_names: &[&str],
+    ) -> Result<DeleteObjectsResult, crate::error::OperationError> {
+        //! Delete multiple files from the R2 bucket.
+        //!
+        //! The files are deleted in batches of up to 1000 keys per request.
+        //! A file that could not be deleted does not fail the whole call,
+        //! but is reported in [DeleteObjectsResult::errors].
+        //! `Err` is only returned when a request itself fails.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_delete_objects_1.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!    object
+        //!        .upload_binary("doctest_operator_delete_objects_2.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // delete files
+        //!    let result = object
+        //!        .delete_objects(&[
+        //!            "doctest_operator_delete_objects_1.txt",
+        //!            "doctest_operator_delete_objects_2.txt",
+        //!        ])
+        //!        .await?;
+        //!
+        //!    println!("deleted: {:?}", result.deleted);
+        //!    println!("errors: {:?}", result.errors);
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("delete_objects", async {
+            let mut result = DeleteObjectsResult::default();
+            for chunk in file_names.chunks(DELETE_OBJECTS_MAX_KEYS) {
+                let mut identifiers = Vec::with_capacity(chunk.len());
+                for file_name in chunk {
+                    match ObjectIdentifier::builder().key(*file_name).build() {
+                        Ok(identifier) => identifiers.push(identifier),
+                        Err(err) => {
+                            return Err(crate::error::OperationError::AWSSdkS3DeleteObjectsError(
+                                crate::error::R2Error::new(err.to_string()),
+                            ))
+                        }
+                    }
+                }
+                let delete = match Delete::builder().set_objects(Some(identifiers)).build() {
+                    Ok(delete) => delete,
+                    Err(err) => {
+                        return Err(crate::error::OperationError::AWSSdkS3DeleteObjectsError(
+                            crate::error::R2Error::new(err.to_string()),
+                        ))
+                    }
+                };
+                let output = match self
+                    .client
+                    .delete_objects()
+                    .set_request_payer(self.request_payer())
+                    .bucket(&self.bucket_name)
+                    .delete(delete)
+                    .customize()
+                    .mutate_request(attach_content_md5)
+                    .send()
+                    .await
+                {
+                    Ok(output) => output,
+                    Err(err) => {
+                        return Err(crate::error::OperationError::AWSSdkS3DeleteObjectsError(
+                            err.into(),
+                        ))
+                    }
+                };
+                for object in output.deleted() {
+                    result
+                        .deleted
+                        .push(object.key().unwrap_or("Unknown").to_owned());
+                }
+                for error in output.errors() {
+                    result.errors.push((
+                        error.key().unwrap_or("Unknown").to_owned(),
+                        format!(
+                            "{}: {}",
+                            error.code().unwrap_or("Unknown"),
+                            error.message().unwrap_or("Unknown error")
+                        ),
+                    ));
+                }
+            }
+            Ok(result)
+        })
+        .await
+    }
+
+    pub async fn delete_all(&self) -> Result<usize, crate::error::OperationError> {
+        //! Delete all files from the R2 bucket.
+        //!
+        //! Return the number of deleted files.
+        //! If some files could not be deleted, the remaining files are still deleted
+        //! and then an error describing the first failure is returned.
+        //! Use [Operator::delete_all_stream] to see the progress and the failures of each batch,
+        //! and [Operator::plan_delete_all] to see the files to be deleted beforehand.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .create_client_result()?;
+        //! // delete all files
+        //! let count: usize = object.delete_all().await?;
+        //!
+        //! println!("{} files were deleted.", count);
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("delete_all", async { self.delete_prefix("").await })
+            .await
+    }
+
+    pub fn delete_all_stream(
+        &self,
+    ) -> impl Stream<Item = Result<DeleteObjectsResult, crate::error::OperationError>> + '_ {
+        //! Delete all files from the R2 bucket, yielding the [DeleteObjectsResult] of each batch.
+        //!
+        //! Listing and deletion happen lazily one page (up to 1000 keys) at a time,
+        //! so memory stays bounded and dropping the stream stops the deletion.
+        //! Each batch (listing a page and deleting its files) is one `delete_all_stream` operation,
+        //! with its own deadline set by [Operator::with_timeout].
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! use futures::StreamExt;
+        //!
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .create_client_result()?;
+        //! // delete all files batch by batch
+        //! let mut batches = std::pin::pin!(object.delete_all_stream());
+        //! while let Some(batch) = batches.next().await {
+        //!     println!("{} files were deleted.", batch?.deleted.len());
+        //! }
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.delete_pages_stream("delete_all_stream", "")
+    }
+
+    pub async fn delete_prefix(&self, prefix: &str) -> Result<usize, crate::error::OperationError> {
+        //! Delete all files whose key starts with `prefix` from the R2 bucket.
+        //!
+        //! Return the number of deleted files.
+        //! If some files could not be deleted, the remaining files are still deleted
+        //! and then an error describing the first failure is returned.
+        //! Use [Operator::plan_delete_prefix] to see the files to be deleted beforehand.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .create_client_result()?;
+        //! // delete all files under "logs/"
+        //! let count: usize = object.delete_prefix("logs/").await?;
+        //!
+        //! println!("{} files were deleted.", count);
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("delete_prefix", async {
+            let mut count = 0;
+            let mut failed = 0;
+            let mut first_error = None;
+            let mut batches = std::pin::pin!(self.delete_prefix_stream(prefix));
+            while let Some(batch) = batches.next().await {
+                let batch = batch?;
+                count += batch.deleted.len();
+                failed += batch.errors.len();
+                if first_error.is_none() {
+                    first_error = batch.errors.into_iter().next();
+                }
+            }
+            match first_error {
+                Some((file_name, message)) => {
+                    Err(crate::error::OperationError::AWSSdkS3DeleteObjectsError(
+                        crate::error::R2Error::new(format!(
+                            "{} files could not be deleted. {}: {}",
+                            failed, file_name, message
+                        )),
+                    ))
+                }
+                None => Ok(count),
+            }
+        })
+        .await
+    }
+
+    pub fn delete_prefix_stream<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Stream<Item = Result<DeleteObjectsResult, crate::error::OperationError>> + 'a {
+        //! Delete all files whose key starts with `prefix` from the R2 bucket,
+        //! yielding the [DeleteObjectsResult] of each batch.
+        //!
+        //! Works the same as [Operator::delete_all_stream] other than the prefix.
+        self.delete_pages_stream("delete_prefix_stream", prefix)
+    }
+
+    /// Delete the files under `prefix` page by page, yielding the result of each batch.
+    ///
+    /// Listing a page and deleting its keys is one `op` operation,
+    /// so each batch has its own deadline and is recorded once.
+    fn delete_pages_stream<'a>(
+        &'a self,
+        op: &'static str,
+        prefix: &'a str,
+    ) -> impl Stream<Item = Result<DeleteObjectsResult, crate::error::OperationError>> + 'a {
+        let pages = self.list_pages(&self.bucket_name, |request| {
+            request
+                .set_prefix(non_empty(prefix))
+                .max_keys(DELETE_OBJECTS_MAX_KEYS as i32)
+        });
+        futures::stream::unfold(pages, move |mut pages| async move {
+            let batch = self
+                .instrument(op, async {
+                    while let Some(output) = pages.next().await {
+                        let output = output?;
+                        let file_names: Vec<&str> = output
+                            .contents()
+                            .iter()
+                            .filter_map(|object| object.key())
+                            .collect();
+                        if !file_names.is_empty() {
+                            return self.delete_objects(&file_names).await.map(Some);
+                        }
+                    }
+                    Ok(None)
+                })
+                .await;
+            match batch {
+                Ok(Some(result)) => Some((Ok(result), pages)),
+                Ok(None) => None,
+                Err(err) => Some((Err(err), pages)),
+            }
+        })
+    }
+
+    pub async fn plan_delete_all(&self) -> Result<Vec<String>, crate::error::OperationError> {
+        //! Get the file names (keys) [Operator::delete_all] would delete, without deleting anything.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .create_client_result()?;
+        //! // check the files before deleting them
+        //! let file_names: Vec<String> = object.plan_delete_all().await?;
+        //! for file_name in &file_names {
+        //!     println!("{} will be deleted.", file_name);
+        //! }
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("plan_delete_all", async { self.list_keys("").await })
+            .await
+    }
+
+    pub async fn plan_delete_prefix(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<String>, crate::error::OperationError> {
+        //! Get the file names (keys) [Operator::delete_prefix] would delete, without deleting anything.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .create_client_result()?;
+        //! // check the files under "logs/" before deleting them
+        //! let file_names: Vec<String> = object.plan_delete_prefix("logs/").await?;
+        //! for file_name in &file_names {
+        //!     println!("{} will be deleted.", file_name);
+        //! }
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("plan_delete_prefix", async { self.list_keys(prefix).await })
+            .await
+    }
+
+    /// List every key under `prefix` in the configured bucket.
+    pub(crate) async fn list_keys(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<String>, crate::error::OperationError> {
+        let mut response = self.list_pages(&self.bucket_name, |request| {
+            request.set_prefix(non_empty(prefix))
+        });
+        let mut keys = Vec::new();
+        while let Some(output) = response.next().await {
+            keys.extend(
+                output?
+                    .contents()
+                    .iter()
+                    .filter_map(|object| object.key())
+                    .map(str::to_owned),
+            );
+        }
+        Ok(keys)
+    }
+
+    pub async fn head_object(
+        &self,
+        file_name: &str,
+    ) -> Result<ObjectMetadata, crate::error::OperationError> {
+        //! Get the metadata of a file in the R2 bucket without downloading it.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_head_object.txt", "test/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // get metadata
+        //!    let metadata = object.head_object("doctest_operator_head_object.txt").await?;
+        //!
+        //!    println!("{} bytes, expiration: {:?}", metadata.size, metadata.expiration);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_head_object.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("head_object", async {
+            let output = self.head(file_name).await?;
+            Ok(object_metadata(&output))
+        })
+        .await
+    }
+
+    pub async fn head_objects(
+        &self,
+        file_names: &[&str],
+    ) -> Result<
+        Vec<(String, Result<ObjectMetadata, crate::error::OperationError>)>,
+        crate::error::OperationError,
+    > {
+        //! Get the metadata of many files in the R2 bucket.
+        //!
+        //! Up to 8 `HeadObject` requests are sent concurrently.
+        //! The results are returned in the same order as `file_names`,
+        //! and a failure of one file does not affect the others.
+        //! The whole batch is one `head_objects` operation, so the outer `Err` is only returned
+        //! when the batch exceeds the deadline set by [Operator::with_timeout].
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_head_objects.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // get the metadata of the files
+        //!    let results = object
+        //!        .head_objects(&["doctest_operator_head_objects.txt", "doctest_operator_head_objects_missing.txt"])
+        //!        .await?;
+        //!
+        //!    for (file_name, metadata) in results {
+        //!        match metadata {
+        //!            Ok(metadata) => println!("{}: {} bytes", file_name, metadata.size),
+        //!            Err(err) => println!("{}: {}", file_name, err),
+        //!        }
+        //!    }
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_head_objects.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("head_objects", async {
+            // Map indices instead of borrowed file names, so that the future stays `Send`.
+            let heads = (0..file_names.len()).map(|index| async move {
+                let file_name = file_names[index];
+                (file_name.to_string(), self.head_object(file_name).await)
+            });
+            let results = futures::stream::iter(heads)
+                .buffered(MAX_CONCURRENCY)
+                .collect()
+                .await;
+            Ok(results)
+        })
+        .await
+    }
+
+    pub async fn content_type(
+        &self,
+        file_name: &str,
+    ) -> Result<Option<String>, crate::error::OperationError> {
+        //! Get the `Content-Type` of a file in the R2 bucket.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_content_type.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // get the content type
+        //!    let content_type: Option<String> = object.content_type("doctest_operator_content_type.txt").await?;
+        //!
+        //!    assert_eq!(content_type.as_deref(), Some("text/plain"));
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_content_type.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("content_type", async {
+            let output = self.head(file_name).await?;
+            Ok(output.content_type().map(str::to_owned))
+        })
+        .await
+    }
+
+    /// Get the deadline of a call starting now and the timeout set by [Operator::with_timeout], if any.
+    fn deadline(&self) -> Option<(tokio::time::Instant, Duration)> {
+        self.timeout
+            .map(|timeout| (tokio::time::Instant::now() + timeout, timeout))
+    }
+
+    /// Start a `ListObjectsV2` request of `bucket_name` listing the keys URL-encoded (`encoding-type=url`),
+    /// so keys containing characters which are invalid in XML (e.g. control characters) are listed correctly.
+    fn list_request(&self, bucket_name: &str) -> ListObjectsV2FluentBuilder {
+        self.client
+            .list_objects_v2()
+            .set_request_payer(self.request_payer())
+            .bucket(bucket_name)
+            .encoding_type(EncodingType::Url)
+    }
+
+    /// Send a `ListObjectsV2` request of `bucket_name` configured by `configure`
+    /// (e.g. the prefix) and return the page with the keys decoded.
+    ///
+    /// Every listing goes through this or [Operator::list_pages], so the keys are decoded the same way.
+    pub(crate) async fn list_page(
+        &self,
+        bucket_name: &str,
+        configure: impl FnOnce(ListObjectsV2FluentBuilder) -> ListObjectsV2FluentBuilder,
+    ) -> Result<ListObjectsV2Output, crate::error::OperationError> {
+        match configure(self.list_request(bucket_name)).send().await {
+            Ok(output) => Ok(decode_listing(output)),
+            Err(err) => Err(crate::error::OperationError::AWSSdkS3ListObjectsV2Error(
+                err.into(),
+            )),
+        }
+    }
+
+    /// Paginate a `ListObjectsV2` request of `bucket_name` configured by `configure`
+    /// and yield every page with the keys decoded.
+    pub(crate) fn list_pages(
+        &self,
+        bucket_name: &str,
+        configure: impl FnOnce(ListObjectsV2FluentBuilder) -> ListObjectsV2FluentBuilder,
+    ) -> impl Stream<Item = Result<ListObjectsV2Output, crate::error::OperationError>> + Unpin + 'static
+    {
+        let pages = configure(self.list_request(bucket_name))
+            .into_paginator()
+            .send();
+        Box::pin(futures::stream::unfold(pages, |mut pages| async move {
+            let page = match pages.next().await? {
+                Ok(output) => Ok(decode_listing(output)),
+                Err(err) => Err(crate::error::OperationError::AWSSdkS3ListObjectsV2Error(
+                    err.into(),
+                )),
+            };
+            Some((page, pages))
+        }))
+    }
+
+    /// Send a `HeadObject` request for `file_name`.
+    pub(crate) async fn head(
+        &self,
+        file_name: &str,
+    ) -> Result<HeadObjectOutput, crate::error::OperationError> {
+        match self
+            .client
+            .head_object()
+            .set_request_payer(self.request_payer())
+            .bucket(&self.bucket_name)
+            .key(file_name)
+            .send()
+            .await
+        {
+            Ok(output) => Ok(output),
+            Err(err) => Err(crate::error::OperationError::AWSSdkS3HeadObjectError(
+                err.into(),
+            )),
+        }
+    }
+
+    pub async fn get_object_acl(
+        &self,
+        file_name: &str,
+    ) -> Result<ObjectAcl, crate::error::OperationError> {
+        //! Get the access control list (ACL) of a file in the R2 bucket.
+        //!
+        //! R2 itself does not support object ACLs, so this is only useful with S3 compatible
+        //! gateways that expose them.
+        //! If the server rejects the request as not implemented, this returns
+        //! [OperationError::NotSupported](crate::error::OperationError::NotSupported).
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::{Error, OperationError};
+        //! #
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .set_region("auto".to_string())
+        //! #     .create_client_result()?;
+        //! match object.get_object_acl("sample.txt").await {
+        //!     Ok(acl) => {
+        //!         for grant in acl.grants {
+        //!             println!("{:?}: {:?}", grant.grantee, grant.permission);
+        //!         }
+        //!     }
+        //!     Err(OperationError::NotSupported(_)) => println!("ACLs are not supported."),
+        //!     Err(err) => return Err(err.into()),
+        //! }
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("get_object_acl", async {
+            match self
+                .client
+                .get_object_acl()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .send()
+                .await
+            {
+                Ok(output) => Ok(ObjectAcl {
+                    owner: output
+                        .owner()
+                        .and_then(|owner| owner.id())
+                        .map(str::to_owned),
+                    grants: output
+                        .grants()
+                        .iter()
+                        .map(|grant| AclGrant {
+                            grantee: grant.grantee().and_then(|grantee| {
+                                grantee
+                                    .id()
+                                    .or(grantee.email_address())
+                                    .or(grantee.uri())
+                                    .map(str::to_owned)
+                            }),
+                            permission: grant.permission().map(|p| p.as_str().to_owned()),
+                        })
+                        .collect(),
+                }),
+                Err(err) => match not_supported(err.into()) {
+                    Ok(message) => Err(crate::error::OperationError::NotSupported(message)),
+                    Err(err) => Err(crate::error::OperationError::AWSSdkS3GetObjectAclError(err)),
+                },
+            }
+        })
+        .await
+    }
+
+    pub async fn put_object_acl(
+        &self,
+        file_name: &str,
+        acl: &str,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Set a canned access control list (ACL) (e.g. `private`, `public-read`) to a file in the R2 bucket.
+        //!
+        //! R2 itself does not support object ACLs, so this is only useful with S3 compatible
+        //! gateways that expose them.
+        //! If the server rejects the request as not implemented, this returns
+        //! [OperationError::NotSupported](crate::error::OperationError::NotSupported).
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::{Error, OperationError};
+        //! #
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .set_region("auto".to_string())
+        //! #     .create_client_result()?;
+        //! match object.put_object_acl("sample.txt", "public-read").await {
+        //!     Ok(()) => {}
+        //!     Err(OperationError::NotSupported(_)) => println!("ACLs are not supported."),
+        //!     Err(err) => return Err(err.into()),
+        //! }
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("put_object_acl", async {
+            match self
+                .client
+                .put_object_acl()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .acl(ObjectCannedAcl::from(acl))
+                .send()
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(err) => match not_supported(err.into()) {
+                    Ok(message) => Err(crate::error::OperationError::NotSupported(message)),
+                    Err(err) => Err(crate::error::OperationError::AWSSdkS3PutObjectAclError(err)),
+                },
+            }
+        })
+        .await
+    }
+
+    pub async fn restore_object(
+        &self,
+        file_name: &str,
+        days: i32,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Restore an archived file in the R2 bucket, keeping the restored copy for `days` days (`RestoreObject`).
+        //!
+        //! The restore runs in the background. Check its progress with
+        //! [ObjectMetadata::restore_status] of [Operator::head_object].
+        //! R2 has no archive tier yet, so this is only useful with backends that have one.
+        //! If the server rejects the request as not implemented, this returns
+        //! [OperationError::NotSupported](crate::error::OperationError::NotSupported).
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! #
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .create_client_result()?;
+        //! object.restore_object("archive/2020.tar", 7).await?;
+        //!
+        //! let metadata = object.head_object("archive/2020.tar").await?;
+        //! if let Some(restore_status) = metadata.restore_status {
+        //!     println!("in progress: {}", restore_status.in_progress);
+        //! }
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("restore_object", async {
+            match self
+                .client
+                .restore_object()
+                .set_request_payer(self.request_payer())
+                .bucket(&self.bucket_name)
+                .key(file_name)
+                .restore_request(RestoreRequest::builder().days(days).build())
+                .send()
+                .await
+            {
+                Ok(_) => Ok(()),
+                Err(err) => match not_supported(err.into()) {
+                    Ok(message) => Err(crate::error::OperationError::NotSupported(message)),
+                    Err(err) => Err(crate::error::OperationError::AWSSdkS3RestoreObjectError(
+                        err,
+                    )),
+                },
+            }
+        })
+        .await
+    }
+
+    pub async fn list_objects(&self) -> Result<Vec<String>, crate::error::OperationError> {
+        //! Get file names vector from the R2 bucket.
+        //!
+        //! The file names are sorted in ascending lexicographic order of their UTF-8 bytes,
+        //! as returned by R2.
+        //! The keys are listed URL-encoded and decoded here, so keys containing control characters
+        //! (e.g. a newline) are returned as they are.
+        //! An empty bucket returns an empty vector.
+        //! Use [Operator::list_objects_reversed] for descending order.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!       .upload_binary("doctest_operator_list_objects.txt", "test/plain", b"Hello, World!", None)
+        //!       .await?;
+        //!
+        //!    // get file names vector
+        //!    let file_names: Vec<String> = object.list_objects().await?;
+        //!
+        //!    for file_name in file_names {
+        //!       println!("{}", file_name);
+        //!    }
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_list_objects.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("list_objects", async {
+            self.list_objects_in(&self.bucket_name).await
+        })
+        .await
+    }
+
+    pub async fn list_objects_limited(
+        &self,
+        max_total: usize,
+    ) -> Result<LimitedList, crate::error::OperationError> {
+        //! Get up to `max_total` file names from the R2 bucket.
+        //!
+        //! Paging stops as soon as `max_total` file names are collected,
+        //! so this returns in bounded time even for a huge bucket.
+        //! [LimitedList::has_more] tells whether more files remain.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::object::LimitedList;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // get at most 100 file names
+        //!    let list: LimitedList = object.list_objects_limited(100).await?;
+        //!
+        //!    println!("{:?}", list.file_names);
+        //!    if list.has_more {
+        //!        println!("more files remain.");
+        //!    }
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("list_objects_limited", async {
+            let mut list = LimitedList::default();
+            if max_total == 0 {
+                return Ok(list);
+            }
+            let mut response = self.list_pages(&self.bucket_name, |request| {
+                request.max_keys(max_total.min(LIST_OBJECTS_MAX_KEYS) as i32)
+            });
+            while let Some(result) = response.next().await {
+                let output = result?;
+                let mut file_names = output.contents().iter().filter_map(|object| object.key());
+                for file_name in file_names.by_ref() {
+                    list.file_names.push(file_name.to_owned());
+                    if list.file_names.len() == max_total {
+                        break;
+                    }
+                }
+                if list.file_names.len() == max_total {
+                    list.has_more =
+                        file_names.next().is_some() || output.is_truncated().unwrap_or(false);
+                    break;
+                }
+            }
+            Ok(list)
+        })
+        .await
+    }
+
+    pub async fn prefix_stats(
+        &self,
+        prefix: &str,
+    ) -> Result<PrefixStats, crate::error::OperationError> {
+        //! Get the exact number and total size of the files whose keys start with `prefix`.
+        //! An empty prefix counts the whole bucket.
+        //!
+        //! R2 does not expose an object count through the S3 API (`HeadBucket` has no count,
+        //! and the bucket metrics are only available from the Cloudflare API),
+        //! so this pages through the whole listing, 1000 keys per request.
+        //! For a cheap hint whether a bucket is very large, use [Operator::list_objects_limited]
+        //! and check [LimitedList::has_more] instead.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::object::PrefixStats;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_prefix_stats/a.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    let stats: PrefixStats = object.prefix_stats("doctest_operator_prefix_stats/").await?;
+        //!    println!("{} files, {} bytes", stats.object_count, stats.total_size);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_prefix_stats/a.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("prefix_stats", async {
+            let mut response = self.list_pages(&self.bucket_name, |request| {
+                request.set_prefix(non_empty(prefix))
+            });
+            let mut stats = PrefixStats::default();
+            while let Some(result) = response.next().await {
+                for object in result?.contents() {
+                    stats.object_count += 1;
+                    stats.total_size += object.size().unwrap_or(0).max(0) as u64;
+                }
+            }
+            Ok(stats)
+        })
+        .await
+    }
+
+    pub async fn for_each_object<F: FnMut(&str)>(
+        &self,
+        prefix: Option<&str>,
+        mut f: F,
+    ) -> Result<(), crate::error::OperationError> {
+        //! Call `f` with each file name (key) in the R2 bucket, optionally only under `prefix`.
+        //!
+        //! The pages are requested one after another and `f` is called as each page arrives,
+        //! so only one page (up to 1000 keys) is kept in memory.
+        //! If a page fails, the error is returned and `f` has already been called for the previous pages.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_for_each_object/a.txt", "text/plain", b"a", None)
+        //!        .await?;
+        //!
+        //!    // count the files without collecting the names
+        //!    let mut count = 0;
+        //!    object
+        //!        .for_each_object(Some("doctest_operator_for_each_object/"), |file_name| {
+        //!            println!("{}", file_name);
+        //!            count += 1;
+        //!        })
+        //!        .await?;
+        //!    assert_eq!(count, 1);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_for_each_object/a.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("for_each_object", async {
+            let mut response = self.list_pages(&self.bucket_name, |request| {
+                request.set_prefix(prefix.and_then(non_empty))
+            });
+            while let Some(result) = response.next().await {
+                for key in result?.contents().iter().filter_map(|object| object.key()) {
+                    f(key);
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn list_objects_page(
+        &self,
+        max_keys: usize,
+        continuation_token: Option<&str>,
+    ) -> Result<ListResult, crate::error::OperationError> {
+        //! Get a single page of up to `max_keys` file names from the R2 bucket.
+        //!
+        //! Pass [ListResult::next_token] of the previous page as `continuation_token`
+        //! to get the next page, or `None` for the first page.
+        //! `max_keys` is capped at 1000 by R2.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::object::ListResult;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // page through the bucket 100 files at a time
+        //!    let mut token: Option<String> = None;
+        //!    loop {
+        //!        let page: ListResult = object.list_objects_page(100, token.as_deref()).await?;
+        //!        println!("{} files: {:?}", page.key_count, page.keys);
+        //!        if !page.is_truncated {
+        //!            break;
+        //!        }
+        //!        token = page.next_token;
+        //!    }
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("list_objects_page", async {
+            let output = self
+                .list_page(&self.bucket_name, |request| {
+                    request
+                        .max_keys(max_keys.min(LIST_OBJECTS_MAX_KEYS) as i32)
+                        .set_continuation_token(continuation_token.map(str::to_owned))
+                })
+                .await?;
+            let keys: Vec<String> = output
+                .contents()
+                .iter()
+                .filter_map(|object| object.key())
+                .map(str::to_owned)
+                .collect();
+            Ok(ListResult {
+                key_count: output
+                    .key_count()
+                    .map(|key_count| key_count as usize)
+                    .unwrap_or(keys.len()),
+                is_truncated: output.is_truncated().unwrap_or(false),
+                next_token: output.next_continuation_token().map(str::to_owned),
+                keys,
+            })
+        })
+        .await
+    }
+
+    pub async fn list_level(
+        &self,
+        prefix: &str,
+    ) -> Result<ListLevel, crate::error::OperationError> {
+        //! Get the sub-folders and the files directly under `prefix`, using `/` as the delimiter.
+        //!
+        //! `prefix` should end with `/` (e.g. `photos/`), or be empty for the top level.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::object::ListLevel;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_list_level/sub/a.txt", "text/plain", b"a", None)
+        //!        .await?;
+        //!    object
+        //!        .upload_binary("doctest_operator_list_level/b.txt", "text/plain", b"b", None)
+        //!        .await?;
+        //!
+        //!    // list like a directory
+        //!    let level: ListLevel = object.list_level("doctest_operator_list_level/").await?;
+        //!
+        //!    assert_eq!(level.folders, vec!["doctest_operator_list_level/sub/"]);
+        //!    assert_eq!(level.files[0].key, "doctest_operator_list_level/b.txt");
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_list_level/sub/a.txt").await?;
+        //!    object.delete("doctest_operator_list_level/b.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("list_level", async {
+            let mut response = self.list_pages(&self.bucket_name, |request| {
+                request.set_prefix(non_empty(prefix)).delimiter("/")
+            });
+            let mut level = ListLevel::default();
+            while let Some(result) = response.next().await {
+                let output = result?;
+                level.folders.extend(
+                    output
+                        .common_prefixes()
+                        .iter()
+                        .filter_map(|common_prefix| common_prefix.prefix())
+                        .map(str::to_owned),
+                );
+                level
+                    .files
+                    .extend(output.contents().iter().filter_map(object_info));
+            }
+            Ok(level)
+        })
+        .await
+    }
+
+    pub async fn list_objects_detailed(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<ObjectInfo>, crate::error::OperationError> {
+        //! Get the files whose keys start with `prefix` in the R2 bucket, with their details.
+        //!
+        //! The owner is fetched (`fetch-owner=true`), and the restore status is requested
+        //! (`x-amz-optional-object-attributes: RestoreStatus`), so they are set where the backend provides them.
+        //! An empty prefix lists the whole bucket.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::object::ObjectInfo;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_list_objects_detailed/a.txt", "text/plain", b"a", None)
+        //!        .await?;
+        //!
+        //!    let files: Vec<ObjectInfo> = object.list_objects_detailed("doctest_operator_list_objects_detailed/").await?;
+        //!    for file in &files {
+        //!        println!("{} {} {:?} {:?}", file.key, file.size, file.owner, file.restore_status);
+        //!    }
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_list_objects_detailed/a.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("list_objects_detailed", async {
+            let mut response = self.list_pages(&self.bucket_name, |request| {
+                request
+                    .set_prefix(non_empty(prefix))
+                    .fetch_owner(true)
+                    .optional_object_attributes(OptionalObjectAttributes::RestoreStatus)
+            });
+            let mut objects = Vec::new();
+            while let Some(result) = response.next().await {
+                objects.extend(result?.contents().iter().filter_map(object_info));
+            }
+            Ok(objects)
+        })
+        .await
+    }
+
+    pub async fn prefix_exists(&self, prefix: &str) -> Result<bool, crate::error::OperationError> {
+        //! Check whether any file whose key starts with `prefix` exists in the R2 bucket.
+        //!
+        //! Only a single `ListObjectsV2` request for one key is sent.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_operator_prefix_exists/a.txt", "text/plain", b"a", None)
+        //!        .await?;
+        //!
+        //!    // check the "folder"
+        //!    assert!(object.prefix_exists("doctest_operator_prefix_exists/").await?);
+        //!    assert!(!object.prefix_exists("doctest_operator_prefix_missing/").await?);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_prefix_exists/a.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("prefix_exists", async {
+            let output = self
+                .list_page(&self.bucket_name, |request| {
+                    request.set_prefix(non_empty(prefix)).max_keys(1)
+                })
+                .await?;
+            Ok(!output.contents().is_empty())
+        })
+        .await
+    }
+
+    pub async fn list_objects_reversed(&self) -> Result<Vec<String>, crate::error::OperationError> {
+        //! Get file names vector from the R2 bucket in descending lexicographic order.
+        //!
+        //! R2 can only list in ascending order, so all file names are collected and then reversed.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!       .upload_binary("doctest_operator_list_objects_reversed.txt", "test/plain", b"Hello, World!", None)
+        //!       .await?;
+        //!
+        //!    // get file names vector (newest key name first)
+        //!    let file_names: Vec<String> = object.list_objects_reversed().await?;
+        //!
+        //!    for file_name in file_names {
+        //!       println!("{}", file_name);
+        //!    }
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_operator_list_objects_reversed.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("list_objects_reversed", async {
+            let mut objects = self.list_objects().await?;
+            objects.reverse();
+            Ok(objects)
+        })
+        .await
+    }
+
+    pub async fn list_objects_in(
+        &self,
+        bucket_name: &str,
+    ) -> Result<Vec<String>, crate::error::OperationError> {
+        //! Get file names vector from the given R2 bucket instead of the configured one.
+        //!
+        //! The file names are sorted in ascending lexicographic order of their UTF-8 bytes.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    let other_bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!
+        //!    // get file names vector
+        //!    let file_names: Vec<String> = object.list_objects_in(&other_bucket_name).await?;
+        //!
+        //!    for file_name in file_names {
+        //!       println!("{}", file_name);
+        //!    }
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("list_objects_in", async {
+            let mut response = self.list_pages(bucket_name, |request| request.max_keys(10));
+            let mut objects = Vec::new();
+            while let Some(result) = response.next().await {
+                for object in result?.contents() {
+                    objects.push(object.key().unwrap_or("Unknown").to_owned());
+                }
+            }
+            Ok(objects)
+        })
+        .await
+    }
+}
+
+/// Default number of attempts, the same as the standard retry mode of aws-sdk-s3.
+pub(crate) const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Maximum number of concurrent requests issued by the batch helpers.
+pub(crate) const MAX_CONCURRENCY: usize = 8;
+
+/// Maximum number of attempts of [Operator::append] when the file was changed concurrently.
+const APPEND_MAX_ATTEMPTS: u32 = 5;
+
+/// Maximum number of keys accepted by a single `DeleteObjects` request.
+const DELETE_OBJECTS_MAX_KEYS: usize = 1000;
+
+/// Maximum number of keys returned by a single `ListObjectsV2` request.
+const LIST_OBJECTS_MAX_KEYS: usize = 1000;
+
+/// Return the message as `Ok` if the server does not implement the operation.
+pub(crate) fn not_supported(err: crate::error::R2Error) -> Result<String, crate::error::R2Error> {
+    if err.status == Some(501)
+        || matches!(err.code, Some(crate::error::S3ErrorCode::NotImplemented))
+    {
+        Ok(err.message)
+    } else {
+        Err(err)
+    }
+}
+
+/// Return the message as `Ok` if the server does not implement object lock,
+/// or object lock is not enabled on the bucket (`InvalidRequest`).
+pub(crate) fn object_lock_not_supported(
+    err: crate::error::R2Error,
+) -> Result<String, crate::error::R2Error> {
+    not_supported(err).or_else(|err| match &err.code {
+        Some(crate::error::S3ErrorCode::Unknown(code)) if code == "InvalidRequest" => {
+            Ok(err.message)
+        }
+        _ => Err(err),
+    })
+}
+
+/// Get the HTTP status code of the raw response of `err`, if any.
+pub(crate) fn status_code<E>(err: &SdkError<E, HttpResponse>) -> Option<u16> {
+    err.raw_response()
+        .map(|response| response.status().as_u16())
+}
+
+/// Convert an error of `GetObject`, surfacing a redirect response as [OperationError::Redirect](crate::error::OperationError::Redirect).
+///
+/// Redirects are not followed, since the signed request would be sent to another host.
+pub(crate) fn get_object_error(
+    err: SdkError<GetObjectError, HttpResponse>,
+) -> crate::error::OperationError {
+    match redirect_location(&err) {
+        Some(location) => crate::error::OperationError::Redirect { location },
+        None => crate::error::OperationError::AWSSdkS3GetObjectError(err.into()),
+    }
+}
+
+/// Get the `Location` header of a redirect raw response of `err`, if any.
+pub(crate) fn redirect_location<E>(err: &SdkError<E, HttpResponse>) -> Option<String> {
+    let response = err.raw_response()?;
+    if !matches!(response.status().as_u16(), 301 | 302 | 303 | 307 | 308) {
+        return None;
+    }
+    response.headers().get("location").map(str::to_string)
+}
+
+/// Attach the `Content-MD5` header computed from the serialized request body.
+///
+/// Some S3 compatible backends reject `DeleteObjects` requests without this header.
+pub(crate) fn attach_content_md5(request: &mut HttpRequest) {
+    if let Some(body) = request.body().bytes() {
+        let digest = STANDARD.encode(Md5::digest(body));
+        request.headers_mut().insert("Content-MD5", digest);
+    }
+}
+
+/// Build the `x-amz-copy-source` value for `file_name` in `bucket_name`.
+///
+/// The key is percent-encoded except for `/` and unreserved characters.
+pub(crate) fn copy_source(bucket_name: &str, file_name: &str) -> String {
+    format!("{}/{}", bucket_name, encode_key(file_name))
+}
+
+/// Percent-encode `file_name` except for `/` and unreserved characters.
+fn encode_key(file_name: &str) -> String {
+    let mut key = String::with_capacity(file_name.len());
+    for byte in file_name.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                key.push(byte as char)
+            }
+            _ => key.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    key
+}
+
+/// Decode a key listed with `encoding-type=url`, where a space may be encoded as `+`.
+///
+/// A key that is not valid percent-encoded UTF-8 is returned as it is.
+pub(crate) fn decode_key(key: &str) -> String {
+    let bytes = key.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let byte = key
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match byte {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => return key.to_owned(),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8(decoded).unwrap_or_else(|_| key.to_owned())
+}
+
+/// Decode the keys and the prefixes of a `ListObjectsV2` response listed with `encoding-type=url`.
+fn decode_listing(mut output: ListObjectsV2Output) -> ListObjectsV2Output {
+    if output.encoding_type.is_none() {
+        return output;
+    }
+    for object in output.contents.iter_mut().flatten() {
+        object.key = object.key.as_deref().map(decode_key);
+    }
+    for common_prefix in output.common_prefixes.iter_mut().flatten() {
+        common_prefix.prefix = common_prefix.prefix.as_deref().map(decode_key);
+    }
+    output.prefix = output.prefix.as_deref().map(decode_key);
+    output.delimiter = output.delimiter.as_deref().map(decode_key);
+    output.start_after = output.start_after.as_deref().map(decode_key);
+    output.encoding_type = None;
+    output
+}
+
+/// Build the value of the `Range` header for `len` bytes starting at `start`.
+fn range_header(start: u64, len: u64) -> Result<String, crate::error::OperationError> {
+    match len.checked_sub(1).and_then(|last| start.checked_add(last)) {
+        Some(end) => Ok(format!("bytes={}-{}", start, end)),
+        None => Err(crate::error::OperationError::InvalidRange {
+            start,
+            end: start.saturating_add(len).saturating_sub(1),
+            size: None,
+        }),
+    }
+}
+
+/// Get the size of the file from the `Content-Range` header (`bytes */<size>`) of a `416 Range Not Satisfiable` response.
+fn unsatisfied_range_size<E>(err: &SdkError<E, HttpResponse>) -> Option<u64> {
+    err.raw_response()?
+        .headers()
+        .get("content-range")?
+        .rsplit('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Detect the mime type of `binary` from its leading bytes.
+#[cfg(feature = "infer")]
+pub(crate) fn detect_content_type(binary: &[u8]) -> &'static str {
+    infer::get(binary)
+        .map(|kind| kind.mime_type())
+        .unwrap_or(OCTET_STREAM)
+}
+
+/// Read up to [MULTIPART_PART_SIZE] bytes from `reader`, returning fewer bytes only at EOF.
+async fn read_part<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Vec<u8>, std::io::Error> {
+    let mut part = Vec::new();
+    reader
+        .take(MULTIPART_PART_SIZE as u64)
+        .read_to_end(&mut part)
+        .await?;
+    Ok(part)
+}
+
+/// Accumulate the chunks of `stream` into parts of exactly `part_size` bytes, except the last one.
+pub(crate) fn chunk_parts<S, E>(
+    stream: S,
+    part_size: usize,
+) -> impl Stream<Item = Result<Vec<u8>, crate::error::OperationError>>
+where
+    S: Stream<Item = Result<Bytes, E>>,
+    E: std::fmt::Display,
+{
+    futures::stream::unfold(
+        Some((Box::pin(stream), Vec::new())),
+        move |state| async move {
+            let (mut stream, mut buffer) = state?;
+            while buffer.len() < part_size {
+                match stream.next().await {
+                    Some(Ok(chunk)) => buffer.extend_from_slice(&chunk),
+                    // end the stream after the error
+                    Some(Err(err)) => {
+                        return Some((
+                            Err(crate::error::OperationError::Other(err.to_string())),
+                            None,
+                        ))
+                    }
+                    None if buffer.is_empty() => return None,
+                    None => return Some((Ok(buffer), None)),
+                }
+            }
+            let rest = buffer.split_off(part_size);
+            Some((Ok(buffer), Some((stream, rest))))
+        },
+    )
+}
+
+/// Compute the MD5 digest of `data`.
+pub(crate) fn md5_digest(data: &[u8]) -> [u8; 16] {
+    Md5::digest(data).into()
+}
+
+/// Compute the MD5 digest of the file at `file_path` without loading it into memory.
+#[cfg(not(target_arch = "wasm32"))]
+async fn file_md5_digest(file_path: &Path) -> Result<[u8; 16], std::io::Error> {
+    let mut file = File::open(file_path).await?;
+    let mut hasher = Md5::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            return Ok(hasher.finalize().into());
+        }
+        hasher.update(&buffer[..read]);
+    }
+}
+
+/// Format `digest` as the ETag of a single part upload.
+pub(crate) fn md5_etag(digest: &[u8; 16]) -> String {
+    format!("\"{}\"", hex(digest))
+}
+
+/// Compute the ETag of a multipart upload: the MD5 of the concatenated part digests and the part count.
+pub(crate) fn multipart_etag(digests: &[[u8; 16]]) -> String {
+    format!(
+        "\"{}-{}\"",
+        hex(&md5_digest(&digests.concat())),
+        digests.len()
+    )
+}
+
+/// Recompute the ETag of data fed in chunks, to compare it with an ETag returned by R2.
+///
+/// A multipart ETag (`"<md5>-<parts>"`) is compared with the part size of this crate,
+/// 8 MiB (the default of the AWS CLI) and the smallest whole MiB giving the same number of parts.
+pub(crate) struct EtagMatcher {
+    e_tag: String,
+    multipart: bool,
+    candidates: Vec<PartHasher>,
+}
+
+/// MD5 digests of the parts of a single candidate part size.
+struct PartHasher {
+    part_size: u64,
+    hasher: Md5,
+    filled: u64,
+    digests: Vec<[u8; 16]>,
+}
+
+impl EtagMatcher {
+    pub(crate) fn new(size: u64, e_tag: &str) -> Self {
+        const MIB: u64 = 1024 * 1024;
+
+        let e_tag = e_tag.trim_matches('"').to_ascii_lowercase();
+        let (multipart, mut part_sizes) = match e_tag.split_once('-') {
+            None => (false, vec![u64::MAX]),
+            Some((_, parts)) => match parts.parse::<u64>() {
+                Ok(parts) if parts > 0 => (
+                    true,
+                    [
+                        part_size(size as usize) as u64,
+                        8 * MIB,
+                        size.div_ceil(parts).div_ceil(MIB).max(1) * MIB,
+                    ]
+                    .into_iter()
+                    .filter(|part_size| size.div_ceil(*part_size).max(1) == parts)
+                    .collect(),
+                ),
+                _ => (true, Vec::new()),
+            },
+        };
+        part_sizes.sort_unstable();
+        part_sizes.dedup();
+        Self {
+            e_tag,
+            multipart,
+            candidates: part_sizes
+                .into_iter()
+                .map(|part_size| PartHasher {
+                    part_size,
+                    hasher: Md5::new(),
+                    filled: 0,
+                    digests: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Whether the ETag can be recomputed at all, i.e. it is an MD5 based ETag.
+    pub(crate) fn is_comparable(&self) -> bool {
+        !self.candidates.is_empty()
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        for candidate in &mut self.candidates {
+            let mut chunk = data;
+            while !chunk.is_empty() {
+                let take = chunk
+                    .len()
+                    .min((candidate.part_size - candidate.filled) as usize);
+                candidate.hasher.update(&chunk[..take]);
+                chunk = &chunk[take..];
+                candidate.filled += take as u64;
+                if candidate.filled == candidate.part_size {
+                    candidate
+                        .digests
+                        .push(candidate.hasher.finalize_reset().into());
+                    candidate.filled = 0;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn matches(self) -> bool {
+        self.candidates.into_iter().any(|mut candidate| {
+            if candidate.filled > 0 || candidate.digests.is_empty() {
+                candidate.digests.push(candidate.hasher.finalize().into());
+            }
+            let actual = match self.multipart {
+                false => md5_etag(&candidate.digests[0]),
+                true => multipart_etag(&candidate.digests),
+            };
+            actual.trim_matches('"') == self.e_tag
+        })
+    }
+}
+
+/// Format `bytes` as lowercase hex.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Strip the surrounding double quotes of an ETag returned by R2 (`"abc123"` to `abc123`).
+pub(crate) fn unquote_etag(e_tag: &str) -> String {
+    e_tag.trim_matches('"').to_string()
+}
+
+/// Wrap an ETag in double quotes for `If-Match` and `If-None-Match`, unless it is already quoted.
+pub(crate) fn quote_etag(e_tag: &str) -> String {
+    format!("\"{}\"", e_tag.trim_matches('"'))
+}
+
+/// Compare the `expected` ETag with the `actual` ETag returned by R2, if `expected` is set.
+///
+/// The ETags of the mismatch error are unquoted.
+pub(crate) fn check_etag(
+    expected: Option<String>,
+    actual: Option<&str>,
+) -> Result<(), crate::error::OperationError> {
+    let expected = match expected {
+        Some(expected) => expected,
+        None => return Ok(()),
+    };
+    let actual = actual.unwrap_or_default();
+    if expected
+        .trim_matches('"')
+        .eq_ignore_ascii_case(actual.trim_matches('"'))
+    {
+        Ok(())
+    } else {
+        Err(crate::error::OperationError::IntegrityMismatch {
+            expected: unquote_etag(&expected),
+            actual: unquote_etag(actual),
+        })
+    }
+}
+
+/// `Content-Type` to use when the type of the data is unknown.
+pub const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Check that `mime_type` is a `type/subtype` MIME type, optionally followed by parameters.
+///
+/// An empty `Content-Type` header is treated inconsistently by clients, so it is rejected.
+pub(crate) fn check_content_type(mime_type: &str) -> Result<(), crate::error::OperationError> {
+    fn is_token(token: &str) -> bool {
+        !token.is_empty()
+            && token
+                .chars()
+                .all(|c| c.is_ascii_graphic() && !"()<>@,;:\\\"/[]?={}".contains(c))
+    }
+    let essence = mime_type.split(';').next().unwrap_or_default().trim();
+    let valid = match essence.split_once('/') {
+        Some((type_, subtype)) => is_token(type_) && is_token(subtype),
+        None => false,
+    };
+    if valid && !mime_type.chars().any(char::is_control) {
+        Ok(())
+    } else {
+        Err(crate::error::OperationError::InvalidContentType(
+            mime_type.to_string(),
+        ))
+    }
+}
+
+/// Convert a `HeadObject` response to [ObjectMetadata].
+pub(crate) fn object_metadata(output: &HeadObjectOutput) -> ObjectMetadata {
+    ObjectMetadata {
+        size: output.content_length().unwrap_or(0) as u64,
+        content_type: output.content_type().map(str::to_owned),
+        cache_control: output.cache_control().map(str::to_owned),
+        e_tag: output.e_tag().map(unquote_etag),
+        last_modified: output
+            .last_modified()
+            .and_then(|last_modified| SystemTime::try_from(*last_modified).ok()),
+        expiration: output.expiration().map(str::to_owned),
+        website_redirect_location: output.website_redirect_location().map(str::to_owned),
+        restore_status: output.restore().map(restore_status),
+    }
+}
+
+/// Parse the `x-amz-restore` header
+/// (e.g. `ongoing-request="false", expiry-date="Fri, 21 Dec 2012 00:00:00 GMT"`).
+pub(crate) fn restore_status(restore: &str) -> RestoreStatus {
+    let expiry_date = restore
+        .split_once("expiry-date=\"")
+        .and_then(|(_, rest)| rest.split_once('"'))
+        .and_then(|(date, _)| DateTime::from_str(date, DateTimeFormat::HttpDate).ok())
+        .and_then(|date| SystemTime::try_from(date).ok());
+    RestoreStatus {
+        in_progress: restore.contains("ongoing-request=\"true\""),
+        expiry_date,
+    }
+}
+
+/// Convert a listed object to [ObjectInfo], skipping an object without a key.
+pub(crate) fn object_info(object: &Object) -> Option<ObjectInfo> {
+    Some(ObjectInfo {
+        key: object.key()?.to_owned(),
+        size: object.size().unwrap_or(0) as u64,
+        e_tag: object.e_tag().map(unquote_etag),
+        last_modified: object
+            .last_modified()
+            .and_then(|last_modified| SystemTime::try_from(*last_modified).ok()),
+        owner: object
+            .owner()
+            .and_then(|owner| owner.display_name().or(owner.id()))
+            .map(str::to_owned),
+        restore_status: object.restore_status().map(|restore_status| RestoreStatus {
+            in_progress: restore_status.is_restore_in_progress().unwrap_or(false),
+            expiry_date: restore_status
+                .restore_expiry_date()
+                .and_then(|expiry_date| SystemTime::try_from(*expiry_date).ok()),
+        }),
+    })
+}
+
+/// Cut the inclusive range `start..=end` out of the whole file `bytes`.
+fn cut_range(bytes: &[u8], start: u64, end: u64) -> Result<Vec<u8>, crate::error::OperationError> {
+    let len = bytes.len() as u64;
+    if start >= len || start > end {
+        return Err(crate::error::OperationError::InvalidRange {
+            start,
+            end,
+            size: Some(len),
+        });
+    }
+    Ok(bytes[start as usize..=end.min(len - 1) as usize].to_vec())
+}
+
+/// Path of a hidden temporary file next to `path`, unique within this process.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn temp_path(path: &Path) -> PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let file_name = path
+        .file_name()
+        .map(|file_name| file_name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        file_name,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Remove a temporary file when dropped while armed, e.g. when a download fails or is cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+struct RemoveOnDrop {
+    path: PathBuf,
+    armed: bool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for RemoveOnDrop {
+    fn drop(&mut self) {
+        if self.armed {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Reader failing with [std::io::ErrorKind::TimedOut] once the deadline set by [Operator::with_timeout] passes.
+#[cfg(not(target_arch = "wasm32"))]
+struct DeadlineReader<R> {
+    reader: Pin<Box<R>>,
+    deadline: Option<(Pin<Box<tokio::time::Sleep>>, Duration)>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<R: AsyncRead> AsyncRead for DeadlineReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        if let Some((sleep, timeout)) = &mut this.deadline {
+            if sleep.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    crate::error::OperationError::Timeout(*timeout),
+                )));
+            }
+        }
+        this.reader.as_mut().poll_read(cx, buf)
+    }
+}
+
+/// Convert an empty `prefix` to `None` so that no `prefix` parameter is sent.
+pub(crate) fn non_empty(prefix: &str) -> Option<String> {
+    if prefix.is_empty() {
+        None
+    } else {
+        Some(prefix.to_string())
     }
 }
@@ -0,0 +1,315 @@
+use aws_sdk_s3::{
+    operation::{
+        copy_object::builders::CopyObjectFluentBuilder,
+        put_object::builders::PutObjectFluentBuilder,
+    },
+    primitives::DateTime,
+    types::{MetadataDirective, ObjectLockLegalHoldStatus},
+};
+use std::time::SystemTime;
+
+/// Checksum algorithm of an upload, computed by the SDK and verified by R2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// CRC32 (`x-amz-checksum-crc32`).
+    Crc32,
+    /// CRC32C (`x-amz-checksum-crc32c`).
+    Crc32c,
+    /// SHA-1 (`x-amz-checksum-sha1`).
+    Sha1,
+    /// SHA-256 (`x-amz-checksum-sha256`).
+    Sha256,
+}
+
+impl From<ChecksumAlgorithm> for aws_sdk_s3::types::ChecksumAlgorithm {
+    fn from(checksum_algorithm: ChecksumAlgorithm) -> Self {
+        match checksum_algorithm {
+            ChecksumAlgorithm::Crc32 => Self::Crc32,
+            ChecksumAlgorithm::Crc32c => Self::Crc32C,
+            ChecksumAlgorithm::Sha1 => Self::Sha1,
+            ChecksumAlgorithm::Sha256 => Self::Sha256,
+        }
+    }
+}
+
+/// Response header overrides applied to a download.
+///
+/// R2 returns the overridden values instead of the ones stored with the object.
+/// This is useful when proxying objects with different headers.
+///
+/// # Example
+///
+/// ```
+/// use cf_r2_sdk::options::ResponseOverrides;
+///
+/// let overrides = ResponseOverrides::new()
+///     .set_content_type("text/plain")
+///     .set_content_disposition("attachment; filename=\"hello.txt\"")
+///     .set_cache_control("max-age=60");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseOverrides {
+    pub(crate) content_type: Option<String>,
+    pub(crate) content_disposition: Option<String>,
+    pub(crate) cache_control: Option<String>,
+}
+
+impl ResponseOverrides {
+    pub fn new() -> Self {
+        //! Create a new [ResponseOverrides] instance without any overrides.
+        Self::default()
+    }
+
+    pub fn set_content_type(mut self, content_type: &str) -> Self {
+        //! Set the `Content-Type` header of the response.
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    pub fn set_content_disposition(mut self, content_disposition: &str) -> Self {
+        //! Set the `Content-Disposition` header of the response.
+        self.content_disposition = Some(content_disposition.to_string());
+        self
+    }
+
+    pub fn set_cache_control(mut self, cache_control: &str) -> Self {
+        //! Set the `Cache-Control` header of the response.
+        self.cache_control = Some(cache_control.to_string());
+        self
+    }
+}
+
+/// Object lock retention mode of an upload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectLockMode {
+    /// Users with the special permission can still delete the file or shorten the retention.
+    Governance,
+    /// Nobody can delete the file or shorten the retention until the retention date.
+    Compliance,
+}
+
+impl From<ObjectLockMode> for aws_sdk_s3::types::ObjectLockMode {
+    fn from(object_lock_mode: ObjectLockMode) -> Self {
+        match object_lock_mode {
+            ObjectLockMode::Governance => Self::Governance,
+            ObjectLockMode::Compliance => Self::Compliance,
+        }
+    }
+}
+
+/// Options applied to an upload.
+///
+/// # Example
+///
+/// ```
+/// use cf_r2_sdk::options::UploadOptions;
+///
+/// let options = UploadOptions::new().set_cache_control("public, max-age=31536000");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UploadOptions {
+    pub(crate) cache_control: Option<String>,
+    pub(crate) expires: Option<SystemTime>,
+    pub(crate) website_redirect_location: Option<String>,
+    pub(crate) checksum_algorithm: Option<ChecksumAlgorithm>,
+    pub(crate) object_lock: Option<(ObjectLockMode, SystemTime)>,
+    pub(crate) legal_hold: Option<bool>,
+}
+
+impl UploadOptions {
+    pub fn new() -> Self {
+        //! Create a new [UploadOptions] instance with default values.
+        //!
+        //! default value of cache control is "no-cache".
+        Self::default()
+    }
+
+    pub fn set_cache_control(mut self, cache_control: &str) -> Self {
+        //! Set the `Cache-Control` header of the object.
+        self.cache_control = Some(cache_control.to_string());
+        self
+    }
+
+    pub fn set_expires(mut self, expires: SystemTime) -> Self {
+        //! Set the `Expires` header of the object.
+        //!
+        //! Older caches that ignore `Cache-Control` use it instead, so it is usually set together
+        //! with [UploadOptions::set_cache_control] to the same lifetime.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::options::UploadOptions;
+        //! use std::time::{Duration, SystemTime};
+        //!
+        //! // cache for a year
+        //! let options = UploadOptions::new()
+        //!     .set_cache_control("public, max-age=31536000")
+        //!     .set_expires(SystemTime::now() + Duration::from_secs(31536000));
+        //! ```
+        self.expires = Some(expires);
+        self
+    }
+
+    pub fn set_website_redirect_location(mut self, website_redirect_location: &str) -> Self {
+        //! Set the `x-amz-website-redirect-location` header of the object.
+        //!
+        //! The object works as a redirect to the given path (e.g. `/new/index.html`) or URL
+        //! (e.g. `https://example.com/`) when the bucket is served as a static website.
+        self.website_redirect_location = Some(website_redirect_location.to_string());
+        self
+    }
+
+    pub fn set_checksum_algorithm(mut self, checksum_algorithm: ChecksumAlgorithm) -> Self {
+        //! Send a checksum of the body computed with `checksum_algorithm` (e.g. `x-amz-checksum-crc32`).
+        //!
+        //! R2 rejects the upload if the checksum does not match the received body.
+        //! This is stronger than the MD5 ETag comparison of
+        //! [Builder::set_verify_integrity](crate::builder::Builder::set_verify_integrity).
+        //!
+        //! default value of checksum algorithm is none.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::options::{ChecksumAlgorithm, UploadOptions};
+        //!
+        //! let options = UploadOptions::new().set_checksum_algorithm(ChecksumAlgorithm::Crc32c);
+        //! ```
+        self.checksum_algorithm = Some(checksum_algorithm);
+        self
+    }
+
+    pub fn set_object_lock(mut self, mode: ObjectLockMode, retain_until: SystemTime) -> Self {
+        //! Lock the object in `mode` until `retain_until`
+        //! (`x-amz-object-lock-mode` and `x-amz-object-lock-retain-until-date`).
+        //!
+        //! Object lock must be enabled on the bucket. An upload rejected because the backend does not
+        //! support object lock returns [OperationError::NotSupported](crate::error::OperationError::NotSupported).
+        //! A CRC32 checksum is sent unless [UploadOptions::set_checksum_algorithm] is set,
+        //! since a checksum is required with object lock.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::options::{ObjectLockMode, UploadOptions};
+        //! use std::time::{Duration, SystemTime};
+        //!
+        //! // keep the file for 7 years
+        //! let retain_until = SystemTime::now() + Duration::from_secs(7 * 365 * 24 * 60 * 60);
+        //! let options = UploadOptions::new()
+        //!     .set_object_lock(ObjectLockMode::Compliance, retain_until)
+        //!     .set_legal_hold(true);
+        //! ```
+        self.object_lock = Some((mode, retain_until));
+        self
+    }
+
+    pub fn set_legal_hold(mut self, legal_hold: bool) -> Self {
+        //! Set the legal hold of the object (`x-amz-object-lock-legal-hold`).
+        //!
+        //! A file under legal hold cannot be deleted until the hold is removed, regardless of the retention.
+        //! See [UploadOptions::set_object_lock] for the requirements.
+        self.legal_hold = Some(legal_hold);
+        self
+    }
+
+    /// Whether any object lock setting is set.
+    pub(crate) fn has_object_lock(&self) -> bool {
+        self.object_lock.is_some() || self.legal_hold.is_some()
+    }
+
+    /// Apply the options to a `PutObject` request.
+    pub(crate) fn apply(&self, request: PutObjectFluentBuilder) -> PutObjectFluentBuilder {
+        request
+            .cache_control(self.cache_control.as_deref().unwrap_or("no-cache"))
+            .set_expires(self.expires.map(DateTime::from))
+            .set_website_redirect_location(self.website_redirect_location.clone())
+            .set_checksum_algorithm(
+                self.checksum_algorithm
+                    .or(self.has_object_lock().then_some(ChecksumAlgorithm::Crc32))
+                    .map(Into::into),
+            )
+            .set_object_lock_mode(self.object_lock.map(|(mode, _)| mode.into()))
+            .set_object_lock_retain_until_date(
+                self.object_lock
+                    .map(|(_, retain_until)| DateTime::from(retain_until)),
+            )
+            .set_object_lock_legal_hold_status(self.legal_hold.map(|legal_hold| match legal_hold {
+                true => ObjectLockLegalHoldStatus::On,
+                false => ObjectLockLegalHoldStatus::Off,
+            }))
+    }
+
+    /// Apply the options to a `CopyObject` request, replacing the metadata of the source.
+    pub(crate) fn apply_copy(&self, request: CopyObjectFluentBuilder) -> CopyObjectFluentBuilder {
+        request
+            .metadata_directive(MetadataDirective::Replace)
+            .cache_control(self.cache_control.as_deref().unwrap_or("no-cache"))
+            .set_expires(self.expires.map(DateTime::from))
+            .set_website_redirect_location(self.website_redirect_location.clone())
+    }
+}
+
+/// Options applied to [Operator::sync_down](crate::operator::Operator::sync_down).
+///
+/// # Example
+///
+/// ```
+/// use cf_r2_sdk::options::SyncDownOptions;
+///
+/// let options = SyncDownOptions::new().set_preserve_mtime(true);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncDownOptions {
+    pub(crate) preserve_mtime: bool,
+}
+
+impl SyncDownOptions {
+    pub fn new() -> Self {
+        //! Create a new [SyncDownOptions] instance with default values.
+        //!
+        //! default value of preserve mtime is false.
+        Self::default()
+    }
+
+    pub fn set_preserve_mtime(mut self, preserve_mtime: bool) -> Self {
+        //! Set the modification time of each downloaded file to the `last_modified` of the object.
+        //!
+        //! When enabled, a file is also downloaded again if its modification time differs.
+        self.preserve_mtime = preserve_mtime;
+        self
+    }
+}
+
+/// Options applied to [Operator::sync_up_with_options](crate::operator::Operator::sync_up_with_options).
+///
+/// # Example
+///
+/// ```
+/// use cf_r2_sdk::options::SyncUpOptions;
+///
+/// let options = SyncUpOptions::new().set_compare_content(true);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncUpOptions {
+    pub(crate) compare_content: bool,
+}
+
+impl SyncUpOptions {
+    pub fn new() -> Self {
+        //! Create a new [SyncUpOptions] instance with default values.
+        //!
+        //! default value of compare content is false.
+        Self::default()
+    }
+
+    pub fn set_compare_content(mut self, compare_content: bool) -> Self {
+        //! Compare the content hash of each file with the ETag of the object, not just the size.
+        //!
+        //! This detects changes that keep the size, at the cost of reading every local file
+        //! whose size matches. Multipart ETags (`"<md5>-<parts>"`) are also compared.
+        self.compare_content = compare_content;
+        self
+    }
+}
@@ -0,0 +1,20 @@
+//! Re-exports of the commonly used types.
+//!
+//! ```
+//! use cf_r2_sdk::prelude::*;
+//!
+//! fn create_operator(endpoint: String) -> Result<Operator, Error> {
+//!     let object: Operator = Builder::new()
+//!         .set_bucket_name("bucket_name".to_string())
+//!         .set_access_key_id("access_key_id".to_string())
+//!         .set_secret_access_key("secret_access_key".to_string())
+//!         .set_endpoint(endpoint)
+//!         .create_client_result()?;
+//!     Ok(object)
+//! }
+//! ```
+pub use crate::builder::Builder;
+pub use crate::error::{BuilderError, Error, OperationError};
+pub use crate::object::{DownloadedObject, ObjectMetadata};
+pub use crate::operator::Operator;
+pub use crate::options::UploadOptions;
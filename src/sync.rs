@@ -0,0 +1,619 @@
+#[cfg(feature = "infer")]
+use crate::operator::detect_content_type;
+#[cfg(not(feature = "infer"))]
+use crate::operator::OCTET_STREAM;
+use crate::{
+    error::OperationError,
+    multipart::MAX_SINGLE_PUT_SIZE,
+    object::ObjectInfo,
+    operator::{object_info, EtagMatcher, Operator},
+    options::{SyncDownOptions, SyncUpOptions, UploadOptions},
+};
+use futures::StreamExt;
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::{fs::File, io::AsyncReadExt};
+
+/// Action taken for a single file during a sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    /// The file was uploaded because it was missing or changed.
+    Uploaded,
+    /// The file was downloaded because it was missing or changed.
+    Downloaded,
+    /// The file was skipped because it was already up to date.
+    Skipped,
+}
+
+/// Summary of a sync.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Number of uploaded files.
+    pub uploaded: usize,
+    /// Number of downloaded files.
+    pub downloaded: usize,
+    /// Number of skipped files.
+    pub skipped: usize,
+    /// Total bytes of the uploaded or downloaded files.
+    pub bytes_transferred: u64,
+    /// Elapsed time of the whole sync.
+    pub elapsed: Duration,
+    /// Action taken for each file, keyed by the object key.
+    pub actions: Vec<(String, SyncAction)>,
+}
+
+impl Operator {
+    pub async fn sync_up(
+        &self,
+        local_dir: impl AsRef<Path>,
+        prefix: &str,
+    ) -> Result<SyncReport, OperationError> {
+        //! Upload the files under `local_dir` to the R2 bucket under `prefix`.
+        //!
+        //! The object key is `prefix` joined with the path relative to `local_dir`.
+        //! Files whose remote object already has the same size are skipped.
+        //! Each file is streamed from the disk (with a multipart upload above 5 GiB),
+        //! with the content type detected from its leading bytes with the `infer` feature,
+        //! or [OCTET_STREAM](crate::operator::OCTET_STREAM) without it.
+        //! Use [Operator::sync_up_with_options] to also compare the content.
+        //! Use [Operator::plan_sync_up] to see the files to be uploaded beforehand.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // sync the data directory
+        //!    let report = object.sync_up("./data", "doctest_sync_sync_up").await?;
+        //!
+        //!    println!("{} uploaded, {} skipped", report.uploaded, report.skipped);
+        //!
+        //!    // clean up
+        //!    for (key, _) in report.actions {
+        //!        object.delete(&key).await?;
+        //!    }
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("sync_up", async {
+            // boxed to keep the layout of the nested futures within the recursion limit
+            Box::pin(self.sync_up_with_options(local_dir, prefix, &SyncUpOptions::new())).await
+        })
+        .await
+    }
+
+    pub async fn sync_up_with_options(
+        &self,
+        local_dir: impl AsRef<Path>,
+        prefix: &str,
+        options: &SyncUpOptions,
+    ) -> Result<SyncReport, OperationError> {
+        //! Upload the files under `local_dir` to the R2 bucket under `prefix`, with options.
+        //!
+        //! With [SyncUpOptions::set_compare_content], a file is skipped only if its content hash
+        //! matches the ETag of the remote object, so changes that keep the size are also uploaded.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::options::SyncUpOptions;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // sync the data directory comparing the content
+        //!    let options = SyncUpOptions::new().set_compare_content(true);
+        //!    let report = object
+        //!        .sync_up_with_options("./data", "doctest_sync_sync_up_with_options", &options)
+        //!        .await?;
+        //!
+        //!    println!("{} uploaded, {} skipped", report.uploaded, report.skipped);
+        //!
+        //!    // clean up
+        //!    for (key, _) in report.actions {
+        //!        object.delete(&key).await?;
+        //!    }
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("sync_up_with_options", async {
+            let started = Instant::now();
+            let mut report = SyncReport::default();
+            let plan = self.plan_sync(local_dir.as_ref(), prefix, options).await?;
+            for (path, key, size, action) in plan {
+                if action == SyncAction::Uploaded {
+                    self.upload_path(&key, &path, None).await?;
+                    report.uploaded += 1;
+                    report.bytes_transferred += size;
+                } else {
+                    report.skipped += 1;
+                }
+                report.actions.push((key, action));
+            }
+            report.elapsed = started.elapsed();
+            Ok(report)
+        })
+        .await
+    }
+
+    pub async fn plan_sync_up(
+        &self,
+        local_dir: impl AsRef<Path>,
+        prefix: &str,
+    ) -> Result<Vec<String>, OperationError> {
+        //! Get the object keys [Operator::sync_up] would upload, without uploading anything.
+        //!
+        //! # Example
+        //!
+        //! ```no_run
+        //! # use cf_r2_sdk::builder::Builder;
+        //! # use cf_r2_sdk::error::Error;
+        //! # #[tokio::main(flavor = "current_thread")]
+        //! # async fn main() -> Result<(), Error> {
+        //! # let object = Builder::new()
+        //! #     .set_bucket_name("bucket_name".to_string())
+        //! #     .set_access_key_id("access_key_id".to_string())
+        //! #     .set_secret_access_key("secret_access_key".to_string())
+        //! #     .set_endpoint("https://account_id.r2.cloudflarestorage.com".to_string())
+        //! #     .create_client_result()?;
+        //! // check the files before syncing them
+        //! let keys: Vec<String> = object.plan_sync_up("./data", "backup").await?;
+        //! for key in &keys {
+        //!     println!("{} will be uploaded.", key);
+        //! }
+        //! # Ok(())
+        //! # }
+        //! ```
+        self.instrument("plan_sync_up", async {
+            Ok(self
+                .plan_sync(local_dir.as_ref(), prefix, &SyncUpOptions::new())
+                .await?
+                .into_iter()
+                .filter(|(_, _, _, action)| *action == SyncAction::Uploaded)
+                .map(|(_, key, _, _)| key)
+                .collect())
+        })
+        .await
+    }
+
+    pub async fn upload_file_as(
+        &self,
+        local_path: impl AsRef<Path>,
+        key_prefix: &str,
+        mime_type: Option<&str>,
+    ) -> Result<String, OperationError> {
+        //! Upload a file to the R2 bucket under `key_prefix`, deriving the key from `local_path`,
+        //! and return the key.
+        //!
+        //! A relative path keeps its directories (`photos/2024/a.jpg` under `backup` is uploaded to
+        //! `backup/photos/2024/a.jpg`), while an absolute path only keeps the file name.
+        //! The file is streamed from the disk with `mime_type`, or, when it is `None`,
+        //! with the content type detected the same as [Operator::sync_up].
+        //! A path with `..` or without a file name returns
+        //! [OperationError::InvalidKey].
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    // uploaded to `doctest_sync_upload_file_as/data/sample.jpg`
+        //!    let key = object
+        //!        .upload_file_as("./data/sample.jpg", "doctest_sync_upload_file_as", Some("image/jpeg"))
+        //!        .await?;
+        //!    assert_eq!(key, "doctest_sync_upload_file_as/data/sample.jpg");
+        //!
+        //!    // clean up
+        //!    object.delete(&key).await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("upload_file_as", async {
+            let local_path = local_path.as_ref();
+            let key = key_for_path(local_path, key_prefix).ok_or_else(|| {
+                OperationError::InvalidKey(format!(
+                    "no key can be derived from {}",
+                    local_path.display()
+                ))
+            })?;
+            self.upload_path(&key, local_path, mime_type).await?;
+            Ok(key)
+        })
+        .await
+    }
+
+    pub async fn sync_down(
+        &self,
+        prefix: &str,
+        local_dir: impl AsRef<Path>,
+        options: &SyncDownOptions,
+    ) -> Result<SyncReport, OperationError> {
+        //! Download the files under the directory `prefix` in the R2 bucket to `local_dir`.
+        //!
+        //! Only the keys starting with `prefix/` are downloaded (e.g. not `backup2/x` for `backup`).
+        //! The local path is `local_dir` joined with the object key relative to `prefix`.
+        //! Each file is streamed to a temporary file that is renamed once complete
+        //! (see [Operator::download_to_file]), so an interrupted sync leaves no truncated file.
+        //! Files whose local copy already has the same size are skipped.
+        //! With [SyncDownOptions::set_preserve_mtime], the modification time of each file
+        //! is set to the `last_modified` of the object and is also compared.
+        //! Keys that would escape `local_dir` (e.g. containing `..`) are skipped.
+        //!
+        //! # Example
+        //!
+        //! ```
+        //! use cf_r2_sdk::builder::Builder;
+        //! use cf_r2_sdk::error::Error;
+        //! use cf_r2_sdk::options::SyncDownOptions;
+        //! use dotenvy::dotenv;
+        //! use std::env;
+        //!
+        //! #[tokio::main(flavor = "current_thread")]
+        //! async fn main() -> Result<(), Error> {
+        //!    // load .env file
+        //!    dotenv().expect(".env file not found.");
+        //!    // insert a environment variable
+        //!    let bucket_name = env::var("BUCKET_NAME").expect("BUCKET_NAME not found in .env file.");
+        //!    let endpoint_url: String =
+        //!        env::var("ENDPOINT_URL").expect("ENDPOINT_URL not found in .env file.");
+        //!    let access_key_id: String =
+        //!        env::var("ACCESS_KEY_ID").expect("ACCESS_KEY_ID not found in .env file.");
+        //!    let secret_access_key: String =
+        //!       env::var("SECRET_ACCESS_KEY").expect("SECRET_ACCESS_KEY not found in .env file.");
+        //!    let region: String = env::var("REGION").expect("REGION not found in .env file.");
+        //!
+        //!    let object: cf_r2_sdk::operator::Operator = Builder::new()
+        //!        .set_bucket_name(bucket_name)
+        //!        .set_access_key_id(access_key_id)
+        //!        .set_secret_access_key(secret_access_key)
+        //!        .set_endpoint(endpoint_url)
+        //!        .set_region(region)
+        //!        .create_client_result()?;
+        //!
+        //!    object
+        //!        .upload_binary("doctest_sync_sync_down/a.txt", "text/plain", b"Hello, World!", None)
+        //!        .await?;
+        //!
+        //!    // sync to a local directory keeping the modification times
+        //!    let options = SyncDownOptions::new().set_preserve_mtime(true);
+        //!    let report = object
+        //!        .sync_down("doctest_sync_sync_down", "./target/doctest_sync_sync_down", &options)
+        //!        .await?;
+        //!
+        //!    println!("{} downloaded, {} skipped", report.downloaded, report.skipped);
+        //!
+        //!    // clean up
+        //!    object.delete("doctest_sync_sync_down/a.txt").await?;
+        //!    Ok(())
+        //! }
+        //! ```
+        self.instrument("sync_down", async {
+            let started = Instant::now();
+            let mut report = SyncReport::default();
+            for object in self.remote_objects(prefix).await? {
+                let path = match relative_key(prefix, &object.key)
+                    .and_then(|relative| local_path(local_dir.as_ref(), relative))
+                {
+                    Some(path) => path,
+                    None => continue,
+                };
+                let up_to_date = match tokio::fs::metadata(&path).await {
+                    Ok(metadata) => {
+                        metadata.len() == object.size
+                            && (!options.preserve_mtime
+                                || same_time(metadata.modified().ok(), object.last_modified))
+                    }
+                    Err(_) => false,
+                };
+                if up_to_date {
+                    report.skipped += 1;
+                    report.actions.push((object.key, SyncAction::Skipped));
+                    continue;
+                }
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                self.download_to_file(&object.key, &path).await?;
+                if let (true, Some(last_modified)) = (options.preserve_mtime, object.last_modified)
+                {
+                    let file = tokio::fs::OpenOptions::new()
+                        .write(true)
+                        .open(&path)
+                        .await?;
+                    file.into_std().await.set_modified(last_modified)?;
+                }
+                report.downloaded += 1;
+                report.bytes_transferred += object.size;
+                report.actions.push((object.key, SyncAction::Downloaded));
+            }
+            report.elapsed = started.elapsed();
+            Ok(report)
+        })
+        .await
+    }
+
+    /// Decide the action for each file under `local_dir`, returning the path, key, size and action.
+    async fn plan_sync(
+        &self,
+        local_dir: &Path,
+        prefix: &str,
+        options: &SyncUpOptions,
+    ) -> Result<Vec<(PathBuf, String, u64, SyncAction)>, OperationError> {
+        let remote: HashMap<String, ObjectInfo> = self
+            .remote_objects(prefix)
+            .await?
+            .into_iter()
+            .map(|object| (object.key.clone(), object))
+            .collect();
+        let mut plan = Vec::new();
+        for (path, relative) in walk(local_dir).await? {
+            let key = join_key(prefix, &relative);
+            let size = tokio::fs::metadata(&path).await?.len();
+            let up_to_date = match remote.get(&key) {
+                Some(object) if object.size == size => {
+                    match (options.compare_content, &object.e_tag) {
+                        (false, _) => true,
+                        (true, Some(e_tag)) => file_matches_etag(&path, size, e_tag).await?,
+                        (true, None) => false,
+                    }
+                }
+                _ => false,
+            };
+            let action = if up_to_date {
+                SyncAction::Skipped
+            } else {
+                SyncAction::Uploaded
+            };
+            plan.push((path, key, size, action));
+        }
+        Ok(plan)
+    }
+
+    /// Collect every object under the directory `prefix` (e.g. `backup/x` but not `backup2/x`).
+    async fn remote_objects(&self, prefix: &str) -> Result<Vec<ObjectInfo>, OperationError> {
+        let directory = join_key(prefix, "");
+        let mut response = self.list_pages(&self.bucket_name, |request| request.prefix(directory));
+        let mut objects = Vec::new();
+        while let Some(result) = response.next().await {
+            objects.extend(result?.contents().iter().filter_map(object_info));
+        }
+        Ok(objects)
+    }
+
+    /// Upload the file at `path` to `key` by streaming it from the disk.
+    ///
+    /// A file larger than a single `PutObject` is uploaded with a multipart upload.
+    /// Without `mime_type`, the type is detected with [file_content_type].
+    pub(crate) async fn upload_path(
+        &self,
+        key: &str,
+        path: &Path,
+        mime_type: Option<&str>,
+    ) -> Result<(), OperationError> {
+        let mime_type = match mime_type {
+            Some(mime_type) => mime_type,
+            None => file_content_type(path).await?,
+        };
+        if tokio::fs::metadata(path).await?.len() > MAX_SINGLE_PUT_SIZE {
+            return self
+                .upload_reader(key, mime_type, File::open(path).await?, None)
+                .await;
+        }
+        self.upload_file_streaming(key, mime_type, path, &UploadOptions::new())
+            .await
+    }
+}
+
+/// Recursively collect the files under `root` with their `/` separated relative paths.
+async fn walk(root: &Path) -> Result<Vec<(PathBuf, String)>, OperationError> {
+    let mut files = Vec::new();
+    let mut directories = vec![root.to_path_buf()];
+    while let Some(directory) = directories.pop() {
+        let mut entries = tokio::fs::read_dir(&directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                directories.push(path);
+            } else {
+                let relative = path
+                    .strip_prefix(root)
+                    .unwrap_or(&path)
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                files.push((path, relative));
+            }
+        }
+    }
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(files)
+}
+
+/// Detect the mime type of the file at `path` from its leading bytes with the `infer` feature,
+/// or return [OCTET_STREAM](crate::operator::OCTET_STREAM) without it.
+pub(crate) async fn file_content_type(path: &Path) -> Result<&'static str, OperationError> {
+    #[cfg(feature = "infer")]
+    {
+        let mut head = Vec::new();
+        File::open(path)
+            .await?
+            .take(8192)
+            .read_to_end(&mut head)
+            .await?;
+        Ok(detect_content_type(&head))
+    }
+    #[cfg(not(feature = "infer"))]
+    {
+        let _ = path;
+        Ok(OCTET_STREAM)
+    }
+}
+
+/// Whether the content of the file at `path` has the ETag `e_tag`.
+pub(crate) async fn file_matches_etag(
+    path: &Path,
+    size: u64,
+    e_tag: &str,
+) -> Result<bool, OperationError> {
+    let mut matcher = EtagMatcher::new(size, e_tag);
+    if !matcher.is_comparable() {
+        return Ok(false);
+    }
+    let mut file = File::open(path).await?;
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            return Ok(matcher.matches());
+        }
+        matcher.update(&buffer[..read]);
+    }
+}
+
+/// Join `prefix` and `relative` with a single `/`.
+pub(crate) fn join_key(prefix: &str, relative: &str) -> String {
+    if prefix.is_empty() {
+        relative.to_string()
+    } else {
+        format!("{}/{}", prefix.trim_end_matches('/'), relative)
+    }
+}
+
+/// Get the key relative to `prefix` of `key`, the reverse of [join_key].
+///
+/// `None` is returned if `key` is not under the directory `prefix`, e.g. `backup2/x` for `backup`.
+pub(crate) fn relative_key<'a>(prefix: &str, key: &'a str) -> Option<&'a str> {
+    if prefix.is_empty() {
+        return Some(key);
+    }
+    key.strip_prefix(prefix.trim_end_matches('/'))?
+        .strip_prefix('/')
+}
+
+/// Derive the key of the file at `path` under `key_prefix`.
+///
+/// A relative path is kept as the `/` separated relative key, and an absolute path only keeps
+/// the file name. `None` is returned for a path without a file name or with `..`.
+pub(crate) fn key_for_path(path: &Path, key_prefix: &str) -> Option<String> {
+    if path.has_root() {
+        let file_name = path.file_name()?.to_string_lossy();
+        return Some(join_key(key_prefix, &file_name));
+    }
+    let mut relative = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::Normal(name) => relative.push(name.to_string_lossy()),
+            _ => return None,
+        }
+    }
+    if relative.is_empty() {
+        return None;
+    }
+    Some(join_key(key_prefix, &relative.join("/")))
+}
+
+/// Join `relative` to `root`, or `None` if it is empty, a folder, or would escape `root`.
+pub(crate) fn local_path(root: &Path, relative: &str) -> Option<PathBuf> {
+    let relative = Path::new(relative);
+    let is_normal = relative
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    if relative.as_os_str().is_empty()
+        || relative.as_os_str().to_string_lossy().ends_with('/')
+        || !is_normal
+    {
+        return None;
+    }
+    Some(root.join(relative))
+}
+
+/// Compare two times in whole seconds, as `last_modified` has no sub-second precision.
+fn same_time(a: Option<SystemTime>, b: Option<SystemTime>) -> bool {
+    let seconds = |time: SystemTime| {
+        time.duration_since(SystemTime::UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .ok()
+    };
+    match (a, b) {
+        (Some(a), Some(b)) => seconds(a) == seconds(b),
+        _ => false,
+    }
+}
@@ -0,0 +1,132 @@
+use aws_sdk_s3::{error::BoxError, primitives::SdkBody};
+use bytes::Bytes;
+use http_body::{Body, Frame, SizeHint};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// Size of the chunks of a throttled upload body, small enough to keep the rate smooth.
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Limit the average bandwidth shared by the clones of an [Operator](crate::operator::Operator).
+///
+/// Each transfer reserves its bytes in order, and waits until the bytes reserved before it
+/// would have been transferred at `bytes_per_sec`.
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    bytes_per_sec: u64,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec: bytes_per_sec.max(1),
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Reserve `bytes` and wait until the reservation starts.
+    pub(crate) async fn acquire(&self, bytes: u64) {
+        let start = self.reserve(bytes, Instant::now());
+        tokio::time::sleep_until(start.into()).await;
+    }
+
+    /// Reserve `bytes` at `now`, returning when the reservation starts.
+    pub(crate) fn reserve(&self, bytes: u64, now: Instant) -> Instant {
+        let mut next = match self.next.lock() {
+            Ok(next) => next,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let start = (*next).max(now);
+        *next = start + Duration::from_secs_f64(bytes as f64 / self.bytes_per_sec as f64);
+        start
+    }
+}
+
+/// Upload body sending the data of `inner` in chunks of [UPLOAD_CHUNK_SIZE] under the rate limit.
+pub(crate) struct ThrottledBody {
+    inner: SdkBody,
+    rate_limiter: Arc<RateLimiter>,
+    /// Data of `inner` which is not sent yet.
+    pending: Bytes,
+    /// Wait for the reservation of the next chunk of `pending`.
+    sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+}
+
+impl ThrottledBody {
+    /// Throttle `body`, throttling again from the start when the request is retried.
+    pub(crate) fn wrap(body: SdkBody, rate_limiter: Arc<RateLimiter>) -> SdkBody {
+        match body.try_clone() {
+            Some(body) => SdkBody::retryable(move || {
+                SdkBody::from_body_1_x(Self::new(
+                    body.try_clone().unwrap_or_else(SdkBody::taken),
+                    rate_limiter.clone(),
+                ))
+            }),
+            None => SdkBody::from_body_1_x(Self::new(body, rate_limiter)),
+        }
+    }
+
+    fn new(inner: SdkBody, rate_limiter: Arc<RateLimiter>) -> Self {
+        Self {
+            inner,
+            rate_limiter,
+            pending: Bytes::new(),
+            sleep: None,
+        }
+    }
+}
+
+impl Body for ThrottledBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(sleep) = &mut this.sleep {
+                ready!(sleep.as_mut().poll(cx));
+                this.sleep = None;
+                let len = this.pending.len().min(UPLOAD_CHUNK_SIZE);
+                return Poll::Ready(Some(Ok(Frame::data(this.pending.split_to(len)))));
+            }
+            if !this.pending.is_empty() {
+                let len = this.pending.len().min(UPLOAD_CHUNK_SIZE);
+                let start = this.rate_limiter.reserve(len as u64, Instant::now());
+                this.sleep = Some(Box::pin(tokio::time::sleep_until(start.into())));
+                continue;
+            }
+            match ready!(Pin::new(&mut this.inner).poll_frame(cx)) {
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => this.pending = data,
+                    Err(frame) => return Poll::Ready(Some(Ok(frame))),
+                },
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.pending.is_empty() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let inner = Body::size_hint(&self.inner);
+        let pending = self.pending.len() as u64;
+        let mut size_hint = SizeHint::new();
+        size_hint.set_lower(inner.lower() + pending);
+        if let Some(upper) = inner.upper() {
+            size_hint.set_upper(upper + pending);
+        }
+        size_hint
+    }
+}
@@ -0,0 +1,154 @@
+//! HTTP client for WASM targets, sending the requests of `aws-sdk-s3` with `reqwest`.
+//!
+//! On `wasm32`, `reqwest` is backed by the `fetch` API of the browser or the worker runtime,
+//! and the time source and the sleep of the SDK are backed by `Date.now()` and `setTimeout`.
+
+use aws_sdk_s3::{
+    config::{
+        http::{HttpRequest, HttpResponse},
+        HttpClient, RuntimeComponents,
+    },
+    primitives::SdkBody,
+};
+use aws_smithy_runtime_api::client::{
+    http::{HttpConnector, HttpConnectorFuture, HttpConnectorSettings, SharedHttpConnector},
+    result::ConnectorError,
+};
+use http_body::Body;
+use std::pin::Pin;
+
+/// [HttpClient] sending each request with a shared [reqwest::Client].
+///
+/// The request body is collected before sending, and the response body is read to the end,
+/// since `fetch` does not support streaming request bodies on every runtime.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FetchHttpClient {
+    client: reqwest::Client,
+}
+
+impl FetchHttpClient {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl HttpClient for FetchHttpClient {
+    fn http_connector(
+        &self,
+        _settings: &HttpConnectorSettings,
+        _components: &RuntimeComponents,
+    ) -> SharedHttpConnector {
+        SharedHttpConnector::new(self.clone())
+    }
+}
+
+impl HttpConnector for FetchHttpClient {
+    fn call(&self, request: HttpRequest) -> HttpConnectorFuture {
+        let client = self.client.clone();
+        let future = async move { send(client, request).await };
+        // the futures of `fetch` are not `Send`, but WASM runs them on a single thread
+        #[cfg(target_arch = "wasm32")]
+        let future = send_wrapper::SendWrapper::new(future);
+        HttpConnectorFuture::new(future)
+    }
+}
+
+/// Send `request` with `client` and read the whole response.
+async fn send(
+    client: reqwest::Client,
+    mut request: HttpRequest,
+) -> Result<HttpResponse, ConnectorError> {
+    let body = collect(request.take_body())
+        .await
+        .map_err(ConnectorError::user)?;
+    let method = reqwest::Method::from_bytes(request.method().as_bytes())
+        .map_err(|err| ConnectorError::user(err.into()))?;
+    let mut builder = client.request(method, request.uri()).body(body);
+    for (name, value) in request.headers() {
+        builder = builder.header(name, value);
+    }
+    let response = builder.send().await.map_err(connector_error)?;
+
+    let status = response.status().as_u16();
+    let headers = response.headers().clone();
+    let body = response.bytes().await.map_err(connector_error)?;
+    let mut http_response = HttpResponse::new(
+        status
+            .try_into()
+            .map_err(|err| ConnectorError::other(Box::new(err), None))?,
+        SdkBody::from(body),
+    );
+    for (name, value) in &headers {
+        if let Ok(value) = value.to_str() {
+            http_response
+                .headers_mut()
+                .append(name.as_str().to_string(), value.to_string());
+        }
+    }
+    Ok(http_response)
+}
+
+/// Collect the data frames of `body`.
+async fn collect(mut body: SdkBody) -> Result<Vec<u8>, aws_sdk_s3::error::BoxError> {
+    if let Some(bytes) = body.bytes() {
+        return Ok(bytes.to_vec());
+    }
+    let mut bytes = Vec::new();
+    while let Some(frame) = futures::future::poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await
+    {
+        if let Ok(data) = frame?.into_data() {
+            bytes.extend_from_slice(&data);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Classify a [reqwest::Error] as a timeout, a user error, or an IO error.
+fn connector_error(err: reqwest::Error) -> ConnectorError {
+    if err.is_timeout() {
+        ConnectorError::timeout(err.into())
+    } else if err.is_builder() || err.is_request() && err.url().is_none() {
+        ConnectorError::user(err.into())
+    } else {
+        ConnectorError::io(err.into())
+    }
+}
+
+/// Time source of the SDK reading `Date.now()`, since `SystemTime::now` panics on `wasm32`.
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug)]
+pub(crate) struct DateTimeSource;
+
+#[cfg(target_arch = "wasm32")]
+impl aws_smithy_async::time::TimeSource for DateTimeSource {
+    fn now(&self) -> std::time::SystemTime {
+        std::time::UNIX_EPOCH + std::time::Duration::from_secs_f64(js_sys::Date::now() / 1000.0)
+    }
+}
+
+/// Sleep of the SDK (for the retry backoff) calling `setTimeout` of the global scope.
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug)]
+pub(crate) struct TimeoutSleep;
+
+#[cfg(target_arch = "wasm32")]
+impl aws_sdk_s3::config::AsyncSleep for TimeoutSleep {
+    fn sleep(&self, duration: std::time::Duration) -> aws_sdk_s3::config::Sleep {
+        use js_sys::wasm_bindgen::{JsCast, JsValue};
+
+        let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+            if let Ok(set_timeout) = js_sys::Reflect::get(&js_sys::global(), &"setTimeout".into()) {
+                let _: Result<JsValue, JsValue> =
+                    set_timeout.unchecked_into::<js_sys::Function>().call2(
+                        &JsValue::NULL,
+                        &JsValue::from(resolve),
+                        &JsValue::from_f64(duration.as_millis() as f64),
+                    );
+            }
+        });
+        let future = wasm_bindgen_futures::JsFuture::from(promise);
+        aws_sdk_s3::config::Sleep::new(send_wrapper::SendWrapper::new(async move {
+            let _ = future.await;
+        }))
+    }
+}